@@ -0,0 +1,9 @@
+#![allow(clippy::type_complexity, clippy::comparison_chain, clippy::manual_is_multiple_of)]
+
+//! Planet simulation of Gaia Maker, usable without the game frontend.
+
+extern crate tile_geom as geom;
+
+pub mod loader;
+pub mod planet;
+pub mod savefile;
@@ -0,0 +1,85 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use fnv::FnvHashMap;
+use serde::Deserialize;
+use serde_with::{DisplayFromStr, Same, serde_as};
+
+use crate::planet::*;
+
+#[serde_as]
+#[derive(Deserialize)]
+#[serde(transparent)]
+struct BiomeList(
+    #[serde_as(as = "FnvHashMap<DisplayFromStr, Same>")] FnvHashMap<Biome, BiomeAttrs>,
+);
+
+/// Load planet parameters from the assets directory without the asset server
+pub fn load_params(assets_dir: &Path) -> Result<Params> {
+    let mut params: Params = read_ron(&assets_dir.join("planet.params.ron"))?;
+    params.biomes = read_ron::<BiomeList>(&assets_dir.join("biomes/list.biomes.ron"))?.0;
+    params.structures = read_ron(&assets_dir.join("structures/list.structures.ron"))?;
+
+    // Start planets
+    for path in files_with_suffix(&assets_dir.join("start_planets"), ".start_planet.ron")? {
+        params.start_planets.push(read_ron(&path)?);
+    }
+    params
+        .start_planets
+        .sort_by(|a, b| match a.habitability.cmp(&b.habitability) {
+            std::cmp::Ordering::Equal => a.id.cmp(&b.id),
+            o => o,
+        });
+
+    // Space buildings
+    for path in files_with_suffix(&assets_dir.join("space_buildings"), ".space_building.ron")? {
+        let file_name = path.file_name().unwrap().to_string_lossy();
+        let id = file_name.strip_suffix(".space_building.ron").unwrap();
+        let kind = match id.parse::<SpaceBuildingKind>() {
+            Ok(kind) => kind,
+            Err(e) => {
+                log::warn!("invalid space building id: {}", e);
+                continue;
+            }
+        };
+        params.space_buildings.insert(kind, read_ron(&path)?);
+    }
+
+    // Animals
+    for path in files_with_suffix(&assets_dir.join("animals"), ".animal.ron")? {
+        let file_name = path.file_name().unwrap().to_string_lossy();
+        let animal_id = file_name.strip_suffix(".animal.ron").unwrap();
+        let animal_id = match AnimalId::from(animal_id) {
+            Ok(animal_id) => animal_id,
+            Err(e) => {
+                log::warn!("invalid string \"{}\" for animal id: {}", animal_id, e);
+                continue;
+            }
+        };
+        params.animals.insert(animal_id, read_ron(&path)?);
+    }
+
+    params.check_biome_ids().map_err(anyhow::Error::msg)?;
+
+    Ok(params)
+}
+
+fn read_ron<T: serde::de::DeserializeOwned>(path: &Path) -> Result<T> {
+    let s = std::fs::read_to_string(path).with_context(|| format!("read {}", path.display()))?;
+    ron::from_str(&s).with_context(|| format!("deserialize {}", path.display()))
+}
+
+fn files_with_suffix(dir: &Path, suffix: &str) -> Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    for entry in std::fs::read_dir(dir).with_context(|| format!("read {}", dir.display()))? {
+        let path = entry?.path();
+        if path
+            .file_name()
+            .is_some_and(|file_name| file_name.to_string_lossy().ends_with(suffix))
+        {
+            files.push(path);
+        }
+    }
+    files.sort();
+    Ok(files)
+}
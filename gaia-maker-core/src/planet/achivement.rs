@@ -32,6 +32,7 @@ pub enum Achivement {
     Deforestation,
     HeavenlyFire,
     ThreeWayDeadlock,
+    RunawayGreenhouse,
 }
 
 pub static ACHIVEMENTS: std::sync::LazyLock<Vec<Achivement>> =
@@ -62,15 +63,15 @@ impl Achivement {
     fn check(self, planet: &Planet, params: &Params, sim: &Sim) -> bool {
         match self {
             Achivement::Grasslands => Requirement::BiomeTiles {
-                biomes: vec![Biome::Grassland],
+                biomes: vec![Biome::new("grassland")],
                 n: 10,
             }
             .check(planet),
             Achivement::Forests => Requirement::BiomeTiles {
                 biomes: vec![
-                    Biome::BorealForest,
-                    Biome::TemperateForest,
-                    Biome::TropicalRainforest,
+                    Biome::new("boreal-forest"),
+                    Biome::new("temperate-forest"),
+                    Biome::new("tropical-rainforest"),
                 ],
                 n: 50,
             }
@@ -90,7 +91,7 @@ impl Achivement {
                     && planet
                         .map
                         .iter()
-                        .filter(|tile| matches!(tile.biome, Biome::IceSheet | Biome::SeaIce))
+                        .filter(|tile| matches!(tile.biome, Biome::ICE_SHEET | Biome::SEA_ICE))
                         .count()
                         == 0
             }
@@ -106,17 +107,15 @@ impl Achivement {
                         .map
                         .iter()
                         .filter(|tile| {
-                            if tile.biome.is_land() {
+                            if tile.biome.is_land(params) {
                                 return false;
                             }
                             for animal in tile.animal {
                                 let Some(animal) = animal else {
                                     continue;
                                 };
-                                let Some(animal_attr) = params.animals.get(&animal.id) else {
-                                    continue;
-                                };
-                                if animal_attr.habitat.match_biome(Biome::Ocean) {
+                                let animal_attr = planet.animal_attr(&animal.id, params);
+                                if animal_attr.habitat.match_biome(Biome::OCEAN, params) {
                                     return true;
                                 }
                             }
@@ -155,7 +154,7 @@ impl Achivement {
             }),
             Achivement::AbundantPower => planet.res.power >= 30000.0,
             Achivement::GiantMirror => Requirement::SpaceBuildingBuilt {
-                kind: SpaceBuildingKind::OrbitalMirror,
+                kind: SpaceBuildingKind::new("orbital-mirror"),
                 n: 1,
             }
             .check(planet),
@@ -179,7 +178,7 @@ impl Achivement {
                     .map
                     .iter_idx()
                     .map(|p| {
-                        if planet.map[p].biome.is_land() {
+                        if planet.map[p].biome.is_land(params) {
                             sim.biomass_consumption[p] as f64
                         } else {
                             0.0
@@ -196,6 +195,9 @@ impl Achivement {
                     .count()
                     == 3
             }
+            Achivement::RunawayGreenhouse => {
+                planet.atmo.partial_pressure(GasKind::WaterVapor) >= 1.0
+            }
             _ => false,
         }
     }
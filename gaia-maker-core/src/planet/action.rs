@@ -1,3 +1,4 @@
+use super::sim::CoordsConverter;
 use super::*;
 
 impl Planet {
@@ -11,15 +12,56 @@ impl Planet {
         Ok(())
     }
 
-    pub fn placeable(&self, p: Coords) -> bool {
-        if !self.map.in_range(p) {
+    /// Check the requirement to unlock the structure
+    pub fn structure_unlocked(&self, kind: StructureKind, params: &Params) -> bool {
+        params.structures[&kind]
+            .unlock
+            .as_ref()
+            .is_none_or(|requirement| requirement.check(self))
+    }
+
+    /// Check the tile is empty and satisfies the placement rules of the structure
+    pub fn placeable(&self, p: Coords, kind: StructureKind, params: &Params) -> bool {
+        if !self.map.in_range(p) || self.map[p].structure.is_some() {
+            return false;
+        }
+        let placement = &params.structures[&kind].placement;
+
+        let surface_ok = match placement.surface {
+            PlacementSurface::Any => true,
+            PlacementSurface::Land => self.map[p].biome.is_land(params),
+            PlacementSurface::Sea => self.map[p].biome.is_sea(params),
+        };
+        if !surface_ok {
             return false;
         }
-        self.map[p].structure.is_none()
+
+        let coords_converter = CoordsConverter::new(self);
+        let adjacent_tiles = || {
+            geom::CHEBYSHEV_DISTANCE_1_COORDS
+                .iter()
+                .filter_map(move |d| coords_converter.conv(p + *d))
+                .map(|p_adj| &self.map[p_adj])
+        };
+        let has_structure = |tile: &Tile, kinds: &[StructureKind]| {
+            tile.structure
+                .as_ref()
+                .is_some_and(|structure| kinds.contains(&structure.kind()))
+        };
+        placement.adjacent.iter().all(|rule| match rule {
+            AdjacencyRule::Sea => adjacent_tiles().any(|tile| tile.biome.is_sea(params)),
+            AdjacencyRule::Land => adjacent_tiles().any(|tile| tile.biome.is_land(params)),
+            AdjacencyRule::Structure(kinds) => {
+                adjacent_tiles().any(|tile| has_structure(tile, kinds))
+            }
+            AdjacencyRule::NoStructure(kinds) => {
+                !adjacent_tiles().any(|tile| has_structure(tile, kinds))
+            }
+        })
     }
 
     pub fn place(&mut self, p: Coords, structure: Structure, sim: &mut Sim, params: &Params) {
-        assert!(self.placeable(p));
+        assert!(self.placeable(p, structure.kind(), params));
 
         let kind = structure.kind();
         self.map[p].structure = Some(structure);
@@ -28,10 +70,14 @@ impl Planet {
         self.update(sim, params);
     }
 
+    pub fn demolishable(&self, p: Coords) -> bool {
+        let structure = &self.map[p].structure;
+        structure.is_some() && !matches!(structure, Some(Structure::Settlement(_)))
+    }
+
     pub fn demolition(&mut self, p: Coords, sim: &mut Sim, params: &Params) -> bool {
-        let structure = &mut self.map[p].structure;
-        if structure.is_some() && !matches!(structure, Some(Structure::Settlement(_))) {
-            *structure = None;
+        if self.demolishable(p) {
+            self.map[p].structure = None;
             self.update(sim, params);
             true
         } else {
@@ -84,6 +130,10 @@ impl Planet {
         self.update(sim, params);
     }
 
+    pub fn tile_event_causable(&self, kind: TileEventKind, params: &Params) -> bool {
+        self.res.enough_to_consume(params.event.tile_event_costs[&kind])
+    }
+
     pub fn cause_tile_event(
         &mut self,
         p: Coords,
@@ -92,7 +142,7 @@ impl Planet {
         params: &Params,
     ) -> bool {
         let cost = params.event.tile_event_costs[&kind];
-        if self.res.enough_to_consume(cost) {
+        if self.tile_event_causable(kind, params) {
             self.res.consume(cost);
             super::tile_event::cause_tile_event(self, p, kind, sim, params);
             self.update(sim, params);
@@ -125,7 +175,7 @@ impl Planet {
         super::civ::civilize_animal(self, params, p, animal_id, true);
     }
 
-    pub fn get_civilizable_animal(&mut self, p: Coords, _params: &Params) -> Option<AnimalId> {
+    pub fn get_civilizable_animal(&self, p: Coords, _params: &Params) -> Option<AnimalId> {
         self.map[p]
             .animal
             .iter()
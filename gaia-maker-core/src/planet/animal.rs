@@ -1,7 +1,10 @@
+use std::sync::atomic::AtomicBool;
+
 use arrayvec::ArrayVec;
 use geom::Direction;
-use misc::{calc_congestion_rate, range_to_livability_trapezoid};
+use misc::{calc_congestion_rate, linear_interpolation, range_to_livability_trapezoid};
 use rand::{Rng, seq::IndexedRandom};
+use sim::CoordsConverter;
 
 use super::*;
 
@@ -21,6 +24,11 @@ pub fn sim_animal(planet: &mut Planet, sim: &mut Sim, params: &Params) {
         return;
     }
 
+    // Disabled civilization during the tutorial is not recorded in the journal
+    if !CIVILIZEABLE.load(std::sync::atomic::Ordering::Relaxed) {
+        planet.journal.invalidate();
+    }
+
     for p in planet.map.iter_idx() {
         for size in AnimalSize::iter() {
             process_each_animal(planet, sim, p, size, params);
@@ -48,18 +56,26 @@ fn process_each_animal(
     size: AnimalSize,
     params: &Params,
 ) {
-    let (animal_id, attr, n) = if let Some(ref mut animal) = planet.map[p].animal[size as usize] {
-        (animal.id, &params.animals[&animal.id], animal.n)
+    let (animal_id, n) = if let Some(ref animal) = planet.map[p].animal[size as usize] {
+        (animal.id, animal.n)
     } else {
         return;
     };
+    let attr = super::species::detached_animal_attr(planet, &animal_id, params);
     let planet_size = planet.map.size();
 
-    // Animal growth
-    let growth_speed = params.sim.animal_growth_speed;
-    let cap = calc_cap(planet, p, attr, params);
+    // Animal growth and decrease by predators
+    let growth_speed = params.sim.animal_growth_speed * attr.growth_speed;
+    let cap = calc_cap(planet, p, animal_id, &attr, params, sim);
     let ratio = n / cap;
-    let dn = growth_speed * ratio * (-ratio + 1.0);
+    let predator_density = density_around(
+        planet,
+        p,
+        sim.food_web.predators(&animal_id),
+        &sim.coords_converter(),
+    );
+    let dn = growth_speed * ratio * (-ratio + 1.0)
+        - params.sim.animal_predation_speed * predator_density * n;
     let dn = dn.clamp(
         -params.sim.animal_growth_speed_max,
         params.sim.animal_growth_speed_max,
@@ -79,10 +95,10 @@ fn process_each_animal(
             if animal_id == other_animal.id {
                 1.0
             } else {
-                let other_attr = &params.animals[&other_animal.id];
+                let other_attr = planet.animal_attr(&other_animal.id, params);
                 if attr
                     .habitat
-                    .compete_at_biome(&other_attr.habitat, planet.map[p].biome)
+                    .compete_at_biome(&other_attr.habitat, planet.map[p].biome, params)
                 {
                     params.sim.animal_congestion_weight_by_other_animals
                 } else {
@@ -99,8 +115,9 @@ fn process_each_animal(
         let mut target_tiles: ArrayVec<Coords, 8> = ArrayVec::new();
         for d in Direction::EIGHT_DIRS {
             if let Some(p_next) = sim.convert_p_cyclic(p + d.as_coords())
-                && can_fisson_to(planet, p_next, attr, params, sim)
-                && calc_cap(planet, p_next, attr, params) > params.sim.animal_extinction_threshold
+                && can_fisson_to(planet, p_next, &attr, params, sim)
+                && calc_cap(planet, p_next, animal_id, &attr, params, sim)
+                    > params.sim.animal_extinction_threshold
             {
                 target_tiles.push(p_next);
             }
@@ -131,7 +148,7 @@ fn process_each_animal(
                     continue;
                 };
                 let target_attr = &params.animals[&evolve_to];
-                let cap = calc_cap(planet, p_adj, target_attr, params);
+                let cap = calc_cap(planet, p_adj, evolve_to, target_attr, params, sim);
                 if cap > 0.0 {
                     planet.map[p_adj].animal[target_attr.size as usize] = Some(Animal {
                         id: evolve_to,
@@ -152,7 +169,9 @@ fn process_each_animal(
             }
 
             return;
-        } else if sim.rng.random_bool(calc_civ_prob(planet, attr, params).into()) {
+        } else if super::species::branch_species(planet, sim, p, size, params) {
+            return;
+        } else if sim.rng.random_bool(calc_civ_prob(planet, &attr, params).into()) {
             // Civilize
             if sim.domain[p].is_none() {
                 super::civ::civilize_animal(planet, params, p, animal_id, false);
@@ -175,7 +194,7 @@ fn process_each_animal(
         if let Some(p_dest) = sim.convert_p_cyclic(p + dir.as_coords()) {
             // If the destination is empty
             if planet.map[p_dest].animal[size as usize].is_none() {
-                let cap_dest = calc_cap(planet, p_dest, attr, params);
+                let cap_dest = calc_cap(planet, p_dest, animal_id, &attr, params, sim);
                 let move_probability = (cap_dest / (cap + 0.001)).clamp(0.0, 1.0);
                 if sim.rng.random_bool(move_probability.into()) {
                     planet.map[p_dest].animal[size as usize] =
@@ -206,22 +225,47 @@ pub fn calc_cap_by_atmo_temp(
         planet.atmo.partial_pressure(GasKind::Oxygen),
     );
 
-    cap_temp * cap_oxygen
+    // Sea water shields animals from UV
+    let cap_uv = if tile.biome.is_land(params) {
+        linear_interpolation(&params.sim.livable_uv_table, planet.tile_uv(p))
+    } else {
+        1.0
+    };
+
+    cap_temp * cap_oxygen * cap_uv
 }
 
-fn calc_cap(planet: &Planet, p: Coords, attr: &AnimalAttr, params: &Params) -> f32 {
+fn calc_cap(
+    planet: &Planet,
+    p: Coords,
+    id: AnimalId,
+    attr: &AnimalAttr,
+    params: &Params,
+    sim: &Sim,
+) -> f32 {
     let tile = &planet.map[p];
 
-    if !attr.habitat.match_biome(tile.biome) {
+    if !attr.habitat.match_biome(tile.biome, params) {
         return 0.0;
     }
 
-    let cap_biomass_or_fertility = if tile.biome.is_land() {
+    // Carnivores can live on carrion at a low density without prey
+    let cap_biomass_or_fertility = if !attr.diet.contains(&Diet::Biomass) {
+        params.sim.animal_cap_scavenging
+    } else if tile.biome.is_land(params) {
         (tile.biomass / params.sim.animal_cap_max_biomass).clamp(0.0, 1.0)
     } else {
         tile.fertility.min(params.sim.animal_cap_max_fertility)
             / params.sim.animal_cap_max_fertility
     };
+    let prey = sim.food_web.prey(&id);
+    let cap_food = if prey.is_empty() {
+        cap_biomass_or_fertility
+    } else {
+        let density = density_around(planet, p, prey, &sim.coords_converter());
+        let cap_prey = (density / params.sim.animal_cap_max_prey).min(1.0);
+        cap_biomass_or_fertility.max(cap_prey)
+    };
 
     let settlement_effect = if matches!(planet.map[p].structure, Some(Structure::Settlement(_))) {
         attr.settlement_effect
@@ -229,13 +273,113 @@ fn calc_cap(planet: &Planet, p: Coords, attr: &AnimalAttr, params: &Params) -> f
         1.0
     };
 
-    cap_biomass_or_fertility
-        * calc_cap_by_atmo_temp(planet, p, attr, params, 0.0)
-        * settlement_effect
+    cap_food * calc_cap_by_atmo_temp(planet, p, attr, params, 0.0) * settlement_effect
+}
+
+/// Average population of the species in the tile and the surrounding tiles
+fn density_around(
+    planet: &Planet,
+    p: Coords,
+    ids: &[AnimalId],
+    coords_converter: &CoordsConverter,
+) -> f32 {
+    if ids.is_empty() {
+        return 0.0;
+    }
+    let mut sum = 0.0;
+    let mut n_tiles = 0;
+    for &d in [Coords(0, 0)].iter().chain(geom::CHEBYSHEV_DISTANCE_1_COORDS) {
+        let Some(p_adj) = coords_converter.conv(p + d) else {
+            continue;
+        };
+        n_tiles += 1;
+        for animal in planet.map[p_adj].animal.iter().flatten() {
+            if ids.contains(&animal.id) {
+                sum += animal.n;
+            }
+        }
+    }
+    sum / n_tiles as f32
+}
+
+/// Biomass density consumed by herbivores on the tile
+pub fn grazing(planet: &Planet, p: Coords, params: &Params) -> f32 {
+    planet.map[p]
+        .animal
+        .iter()
+        .flatten()
+        .map(|animal| {
+            let attr = planet.animal_attr(&animal.id, params);
+            if attr.diet.contains(&Diet::Biomass) {
+                params.sim.animal_grazing[attr.size as usize] * animal.n
+            } else {
+                0.0
+            }
+        })
+        .sum()
+}
+
+/// Predator-prey relations between animal species
+#[derive(Clone, Default, Debug)]
+pub struct FoodWeb {
+    prey: FnvHashMap<AnimalId, Vec<AnimalId>>,
+    predators: FnvHashMap<AnimalId, Vec<AnimalId>>,
 }
 
+impl FoodWeb {
+    pub fn new(planet: &Planet, params: &Params) -> Self {
+        let biomes = params.biome_list();
+        let mut food_web = Self::default();
+
+        for (id, attr) in planet.animal_list(params) {
+            for (prey_id, prey_attr) in planet.animal_list(params) {
+                if prey_id == id {
+                    continue;
+                }
+                let eats = attr.diet.iter().any(|diet| match diet {
+                    Diet::Biomass => false,
+                    Diet::Animal(target) => *target == planet.origin_species(prey_id),
+                    Diet::Size(size) => {
+                        *size == prey_attr.size
+                            && biomes.iter().any(|biome| {
+                                attr.habitat
+                                    .compete_at_biome(&prey_attr.habitat, *biome, params)
+                            })
+                    }
+                });
+                if eats {
+                    food_web.prey.entry(id).or_default().push(prey_id);
+                    food_web.predators.entry(prey_id).or_default().push(id);
+                }
+            }
+        }
+        for list in food_web
+            .prey
+            .values_mut()
+            .chain(food_web.predators.values_mut())
+        {
+            list.sort_unstable();
+        }
+
+        food_web
+    }
+
+    /// Species eaten by the animal
+    pub fn prey(&self, id: &AnimalId) -> &[AnimalId] {
+        self.prey.get(id).map_or(&[], |list| list)
+    }
+
+    /// Species eating the animal
+    pub fn predators(&self, id: &AnimalId) -> &[AnimalId] {
+        self.predators.get(id).map_or(&[], |list| list)
+    }
+}
+
+/// Animals can be civilized randomly if true. Disabled during the tutorial
+pub static CIVILIZEABLE: AtomicBool = AtomicBool::new(true);
+
 fn calc_civ_prob(planet: &Planet, attr: &AnimalAttr, params: &Params) -> f32 {
-    if !crate::tutorial::CIVILIZEABLE.load(std::sync::atomic::Ordering::Relaxed) {
+    if !CIVILIZEABLE.load(std::sync::atomic::Ordering::Relaxed) {
         return 0.0;
     }
 
@@ -245,7 +389,7 @@ fn calc_civ_prob(planet: &Planet, attr: &AnimalAttr, params: &Params) -> f32 {
             params.sim.civ_prob_factor_by_existing_civs[civ.current_age() as usize]
     }
 
-    let biome_factor = if attr.habitat.match_biome(Biome::Ocean) {
+    let biome_factor = if attr.habitat.match_biome(Biome::OCEAN, params) {
         params.sim.civ_prob_aquatic
     } else {
         1.0
@@ -278,15 +422,15 @@ fn can_fisson_to(
 }
 
 impl AnimalHabitat {
-    pub fn match_biome(&self, biome: Biome) -> bool {
+    pub fn match_biome(&self, biome: Biome, params: &Params) -> bool {
         match self {
-            Self::Land => biome.is_land(),
-            Self::Sea => biome.is_sea(),
+            Self::Land => biome.is_land(params),
+            Self::Sea => biome.is_sea(params),
             Self::Biomes(biomes) => biomes.contains(&biome),
         }
     }
 
-    pub fn compete_at_biome(&self, other: &Self, biome: Biome) -> bool {
-        self.match_biome(biome) && other.match_biome(biome)
+    pub fn compete_at_biome(&self, other: &Self, biome: Biome, params: &Params) -> bool {
+        self.match_biome(biome, params) && other.match_biome(biome, params)
     }
 }
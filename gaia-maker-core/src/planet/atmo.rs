@@ -0,0 +1,225 @@
+use std::sync::LazyLock;
+
+use fnv::FnvHashMap;
+use serde::{Deserialize, Serialize};
+
+use super::misc::linear_interpolation;
+use super::*;
+
+const MOLECULAR_WEIGHT_N2: f32 = 28.0;
+const MOLECULAR_WEIGHT_O2: f32 = 32.0;
+const MOLECULAR_WEIGHT_CO2: f32 = 44.0;
+const MOLECULAR_WEIGHT_ARGON: f32 = 40.0;
+const MOLECULAR_WEIGHT_CH4: f32 = 16.0;
+const MOLECULAR_WEIGHT_H2O: f32 = 18.0;
+
+pub const CO2_CARBON_WEIGHT_RATIO: f32 = MOLECULAR_WEIGHT_CO2 / 12.0;
+pub const CO2_OXYGEN_WEIGHT_RATIO: f32 = MOLECULAR_WEIGHT_CO2 / MOLECULAR_WEIGHT_O2;
+pub const CH4_CARBON_WEIGHT_RATIO: f32 = MOLECULAR_WEIGHT_CH4 / 12.0;
+
+static GAS_MOLECULAR_WEIGHT: LazyLock<FnvHashMap<GasKind, f32>> = LazyLock::new(|| {
+    let mut map = FnvHashMap::default();
+    map.insert(GasKind::Nitrogen, MOLECULAR_WEIGHT_N2);
+    map.insert(GasKind::Oxygen, MOLECULAR_WEIGHT_O2);
+    map.insert(GasKind::CarbonDioxide, MOLECULAR_WEIGHT_CO2);
+    map.insert(GasKind::Argon, MOLECULAR_WEIGHT_ARGON);
+    map.insert(GasKind::Methane, MOLECULAR_WEIGHT_CH4);
+    map.insert(GasKind::WaterVapor, MOLECULAR_WEIGHT_H2O);
+    map
+});
+
+pub fn molecular_weight(kind: GasKind) -> f32 {
+    GAS_MOLECULAR_WEIGHT[&kind]
+}
+
+pub const AEROSOL_EQUILIBRIUM_TARGET: f32 = 1.0;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Atmosphere {
+    atm: f32,
+    /// Gases mass [Mt]
+    #[serde(deserialize_with = "fill_missing_gases")]
+    mass: FnvHashMap<GasKind, f64>,
+    /// Gass mole ratio
+    #[serde(deserialize_with = "fill_missing_gases")]
+    pub mole_ratio: FnvHashMap<GasKind, f32>,
+    /// Average cloud amount of tiles [%]. The average is 50%
+    pub cloud_amount: f32,
+    /// Average aerosol amount of tiles
+    pub aerosol: f32,
+    /// Ozone column [DU]
+    #[serde(default)]
+    pub ozone: f32,
+    /// Ozone layer damage ratio by nuclear explosions or ion irradiation
+    #[serde(default)]
+    pub ozone_damage: f32,
+    /// Surface UV relative to the present Earth
+    #[serde(default)]
+    pub surface_uv: f32,
+    /// Mass of gases escaped to space in the last cycle [Mt]
+    #[serde(default)]
+    pub escape_rate: f32,
+}
+
+impl Atmosphere {
+    pub fn new(start_params: &StartParams, params: &Params) -> Self {
+        let mass = GasKind::iter()
+            .map(|gas_kind| {
+                let atm = start_params.atmo.get(&gas_kind).copied().unwrap_or(0.0);
+                (
+                    gas_kind,
+                    atm * params.sim.mol_per_atm as f64 * GAS_MOLECULAR_WEIGHT[&gas_kind] as f64,
+                )
+            })
+            .collect();
+        let mole_ratio = GasKind::iter().map(|gas_kind| (gas_kind, 0.0)).collect();
+
+        Atmosphere {
+            atm: 0.0,
+            mass,
+            mole_ratio,
+            cloud_amount: 50.0,
+            aerosol: AEROSOL_EQUILIBRIUM_TARGET,
+            ozone: 0.0,
+            ozone_damage: 0.0,
+            surface_uv: 0.0,
+            escape_rate: 0.0,
+        }
+    }
+
+    pub fn total_mass(&self) -> f32 {
+        self.mass.values().sum::<f64>() as f32
+    }
+
+    pub fn atm(&self) -> f32 {
+        self.atm
+    }
+
+    pub fn partial_pressure(&self, kind: GasKind) -> f32 {
+        self.atm * self.mole_ratio[&kind]
+    }
+
+    pub fn mass(&self, kind: GasKind) -> f32 {
+        *self.mass.get(&kind).unwrap() as f32
+    }
+
+    pub fn set_mass(&mut self, kind: GasKind, value: f32) {
+        *self.mass.get_mut(&kind).unwrap() = value as f64;
+    }
+
+    pub fn add(&mut self, kind: GasKind, value: impl Into<f64>) {
+        let mass = self.mass.get_mut(&kind).unwrap();
+        *mass = (*mass + value.into()).max(0.0);
+    }
+
+    pub fn remove_carbon(&mut self, value: f32) -> bool {
+        debug_assert!(value >= 0.0);
+        let co2_mass = value * CO2_CARBON_WEIGHT_RATIO;
+        let co2_mass_in_atmo = self.mass.get_mut(&GasKind::CarbonDioxide).unwrap();
+        if *co2_mass_in_atmo > co2_mass as f64 {
+            *co2_mass_in_atmo -= co2_mass as f64;
+            self.add(GasKind::Oxygen, value * CO2_OXYGEN_WEIGHT_RATIO);
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn release_carbon(&mut self, value: f32) {
+        self.add(GasKind::Oxygen, -value * CO2_OXYGEN_WEIGHT_RATIO);
+        self.add(GasKind::CarbonDioxide, value * CO2_CARBON_WEIGHT_RATIO);
+    }
+
+    /// Release carbon as methane without consuming oxygen
+    pub fn release_carbon_as_methane(&mut self, value: f32) {
+        self.add(GasKind::Methane, value * CH4_CARBON_WEIGHT_RATIO);
+    }
+
+    pub fn remove_atmo(&mut self, value: impl Into<f64>) {
+        let value = value.into();
+        let total_mass = self.total_mass() as f64;
+        for kind in GasKind::iter() {
+            let mass = self.mass.get_mut(&kind).unwrap();
+            let v = value * *mass / total_mass;
+            *mass = (*mass - v).max(0.0);
+        }
+    }
+}
+
+pub fn sim_atmosphere(planet: &mut Planet, sim: &mut Sim, params: &Params) {
+    oxidize_methane(planet, params);
+    super::escape::sim_atmospheric_escape(planet, params);
+
+    let mut atmo_mole = FnvHashMap::default();
+    let mut sum_mole = 0.0;
+    for gas_kind in GasKind::iter() {
+        let mole = planet.atmo.mass[&gas_kind] as f32 / GAS_MOLECULAR_WEIGHT[&gas_kind];
+        atmo_mole.insert(gas_kind, mole);
+        sum_mole += mole;
+    }
+    for gas_kind in GasKind::iter() {
+        planet
+            .atmo
+            .mole_ratio
+            .insert(gas_kind, atmo_mole[&gas_kind] / sum_mole);
+    }
+    planet.atmo.atm = sum_mole / params.sim.mol_per_atm;
+
+    update_ozone(planet, params);
+
+    super::cloud::sim_aerosol_and_cloud(planet, sim, params);
+}
+
+/// Ozone is formed from oxygen by stellar UV, and shields the surface from UV
+fn update_ozone(planet: &mut Planet, params: &Params) {
+    let relative_solar_power =
+        planet.state.solar_power / params.default_start_params.basics.solar_constant;
+    planet.atmo.ozone_damage *= 1.0 - params.sim.ozone_recovery_rate;
+    planet.atmo.ozone = linear_interpolation(
+        &params.sim.ozone_o2_table,
+        planet.atmo.partial_pressure(GasKind::Oxygen),
+    ) * relative_solar_power.sqrt()
+        * (1.0 - planet.atmo.ozone_damage);
+    planet.atmo.surface_uv = relative_solar_power
+        * linear_interpolation(&params.sim.surface_uv_ozone_table, planet.atmo.ozone);
+}
+
+impl Planet {
+    /// Surface UV at the tile, stronger at low latitudes
+    pub fn tile_uv(&self, p: Coords) -> f32 {
+        self.atmo.surface_uv * self.calc_longitude_latitude(p).1.cos()
+    }
+}
+
+/// Methane is oxidized to carbon dioxide (CH4 + 2O2 -> CO2 + 2H2O)
+fn oxidize_methane(planet: &mut Planet, params: &Params) {
+    let ratio = linear_interpolation(
+        &params.sim.methane_oxidation_o2_table,
+        planet.atmo.partial_pressure(GasKind::Oxygen),
+    );
+    let o2_per_ch4 = 2.0 * MOLECULAR_WEIGHT_O2 / MOLECULAR_WEIGHT_CH4;
+    let ch4 = (planet.atmo.mass(GasKind::Methane) * ratio)
+        .min(planet.atmo.mass(GasKind::Oxygen) / o2_per_ch4);
+    if ch4 <= 0.0 {
+        return;
+    }
+    planet.atmo.add(GasKind::Methane, -ch4);
+    planet.atmo.add(GasKind::Oxygen, -ch4 * o2_per_ch4);
+    planet.atmo.add(
+        GasKind::CarbonDioxide,
+        ch4 * MOLECULAR_WEIGHT_CO2 / MOLECULAR_WEIGHT_CH4,
+    );
+}
+
+/// Fill gases missing in the data saved by older versions
+fn fill_missing_gases<'de, D, V>(deserializer: D) -> Result<FnvHashMap<GasKind, V>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    V: Deserialize<'de> + Default,
+{
+    let mut map = FnvHashMap::<GasKind, V>::deserialize(deserializer)?;
+    for gas_kind in GasKind::iter() {
+        map.entry(gas_kind).or_default();
+    }
+    Ok(map)
+}
@@ -19,12 +19,19 @@ pub fn sim_biome(planet: &mut Planet, sim: &mut Sim, params: &Params) {
 
     for p in map_iter_idx {
         let effect = &mut sim.fertility_value_and_effect[p].1;
-        if let Some(&BuildingEffect::Fertilize {
-            increment,
-            max,
-            range,
-        }) = planet.working_building_effect(p, params)
-        {
+        if let Some(Structure::Settlement(Settlement { age, .. })) = &planet.map[p].structure {
+            *effect -=
+                planet.map[p].fertility * params.sim.fertility_settlement_impact[*age as usize];
+        }
+        for building_effect in planet.working_building_effects(p, params) {
+            let &BuildingEffect::Fertilize {
+                increment,
+                max,
+                range,
+            } = building_effect
+            else {
+                continue;
+            };
             for (_, p) in CDistRangeIter::new(p, range as _) {
                 if let Some(p) = coords_converter.conv(p)
                     && planet.map.in_range(p)
@@ -33,10 +40,6 @@ pub fn sim_biome(planet: &mut Planet, sim: &mut Sim, params: &Params) {
                     *effect += increment;
                 }
             }
-        } else if let Some(Structure::Settlement(Settlement { age, .. })) = &planet.map[p].structure
-        {
-            *effect -=
-                planet.map[p].fertility * params.sim.fertility_settlement_impact[*age as usize];
         }
     }
 
@@ -83,7 +86,7 @@ pub fn sim_biome(planet: &mut Planet, sim: &mut Sim, params: &Params) {
             diff * params.sim.fertility_base_decrement
         };
 
-        let sea_effect = if planet.map[p].biome.is_sea() {
+        let sea_effect = if planet.map[p].biome.is_sea(params) {
             -params.sim.sea_fertility_attenuation_factor * fertility
         } else {
             0.0
@@ -155,7 +158,12 @@ pub fn sim_biome(planet: &mut Planet, sim: &mut Sim, params: &Params) {
         } else {
             diff_to_max * params.sim.base_biomass_decrease_speed
         };
-        let diff = (diff - sim.biomass_consumption[p] / density_to_mass).max(-biomass);
+        let grazing = if planet.map[p].biome.is_land(params) {
+            super::animal::grazing(planet, p, params)
+        } else {
+            0.0
+        };
+        let diff = (diff - sim.biomass_consumption[p] / density_to_mass - grazing).max(-biomass);
         let diff = if diff > 0.0 && sim.domain[p].is_some() {
             diff * params.sim.biomass_increase_speed_factor_by_settlements
         } else {
@@ -175,14 +183,28 @@ pub fn sim_biome(planet: &mut Planet, sim: &mut Sim, params: &Params) {
             }
             sum_biomass += *biomass as f64;
         } else {
+            let released_carbon = carbon_weight * (1.0 - biomass_to_buried_carbon_ratio);
             planet
                 .atmo
-                .release_carbon(carbon_weight * (1.0 - biomass_to_buried_carbon_ratio));
+                .release_carbon(released_carbon * (1.0 - params.sim.biomass_decay_methane_ratio));
+            planet.atmo.release_carbon_as_methane(
+                released_carbon * params.sim.biomass_decay_methane_ratio,
+            );
             *biomass += diff;
             sum_biomass += *biomass as f64;
             planet.map[p].buried_carbon += carbon_weight * biomass_to_buried_carbon_ratio;
         }
         sum_buried_carbon += planet.map[p].buried_carbon as f64;
+
+        // Wetlands emit methane from decomposed biomass
+        if planet.map[p].biome.is_land(params)
+            && sim.humidity[p] >= params.sim.wetland_humidity_threshold
+        {
+            let emitted = planet.map[p].biomass * params.sim.wetland_methane_emission;
+            planet.map[p].biomass -= emitted;
+            sum_biomass -= emitted as f64;
+            planet.atmo.release_carbon_as_methane(emitted * density_to_mass);
+        }
     }
     planet.stat.sum_biomass = sum_biomass as f32 * density_to_mass;
     planet.stat.sum_buried_carbon = sum_buried_carbon as f32;
@@ -191,9 +213,11 @@ pub fn sim_biome(planet: &mut Planet, sim: &mut Sim, params: &Params) {
     process_biome_transition(planet, sim, params);
 }
 
-fn process_biome_transition(planet: &mut Planet, sim: &Sim, params: &Params) {
-    planet.map.par_iter_mut().for_each(|tile| {
-        let mut rng = misc::get_rng();
+fn process_biome_transition(planet: &mut Planet, sim: &mut Sim, params: &Params) {
+    let seed: u64 = sim.rng.random();
+    let sim = &*sim;
+    planet.map.par_iter_mut().enumerate().for_each(|(i, tile)| {
+        let mut rng = misc::get_seeded_rng(seed, i as u64);
         let current_biome = tile.biome;
         let current_priority = if check_requirements(tile, current_biome, params) {
             params.biomes[&current_biome].priority
@@ -201,13 +225,30 @@ fn process_biome_transition(planet: &mut Planet, sim: &Sim, params: &Params) {
             0
         };
 
-        if current_biome.is_sea() {
-            let sea_ice_temp = params.biomes[&Biome::SeaIce].requirements.temp.1;
-            let next_biome = if tile.temp < sea_ice_temp {
-                Biome::SeaIce
+        if current_biome.is_sea(params) {
+            let sea_ice_temp = params.biomes[&Biome::SEA_ICE].requirements.temp.1;
+            let base_biome = if tile.temp < sea_ice_temp {
+                Biome::SEA_ICE
             } else {
-                Biome::Ocean
+                Biome::OCEAN
             };
+            // Additional sea biomes replace the base biome when their requirements are satisfied
+            let next_biome = params
+                .biomes
+                .iter()
+                .filter_map(|(&biome, attrs)| {
+                    if attrs.category == BiomeCategory::Sea
+                        && biome != Biome::OCEAN
+                        && biome != Biome::SEA_ICE
+                        && check_requirements(tile, biome, params)
+                    {
+                        Some((attrs.priority, biome))
+                    } else {
+                        None
+                    }
+                })
+                .max()
+                .map_or(base_biome, |(_, biome)| biome);
             if current_biome != next_biome {
                 let transition_probability = if sim.before_start {
                     params.sim.before_start_biome_transition_probability
@@ -222,16 +263,19 @@ fn process_biome_transition(planet: &mut Planet, sim: &Sim, params: &Params) {
         }
 
         if tile.ice >= params.sim.ice_thickness_of_ice_sheet {
-            if current_biome != Biome::IceSheet {
-                tile.biome = Biome::IceSheet;
+            if current_biome != Biome::ICE_SHEET {
+                tile.biome = Biome::ICE_SHEET;
             }
             return;
         }
 
-        let Some((_, next_biome)) = Biome::iter()
-            .filter_map(|biome| {
-                let priority = params.biomes[&biome].priority;
-                if biome != current_biome
+        let Some((_, next_biome)) = params
+            .biomes
+            .iter()
+            .filter_map(|(&biome, attrs)| {
+                let priority = attrs.priority;
+                if attrs.category == BiomeCategory::Land
+                    && biome != current_biome
                     && priority > current_priority
                     && check_requirements(tile, biome, params)
                 {
@@ -240,7 +284,7 @@ fn process_biome_transition(planet: &mut Planet, sim: &Sim, params: &Params) {
                     None
                 }
             })
-            .max_by_key(|(priority, _)| *priority)
+            .max()
         else {
             return;
         };
@@ -265,7 +309,7 @@ fn process_biome_transition(planet: &mut Planet, sim: &Sim, params: &Params) {
 }
 
 fn check_requirements(tile: &Tile, biome: Biome, params: &Params) -> bool {
-    if biome == Biome::IceSheet && tile.ice <= params.sim.ice_thickness_of_ice_sheet {
+    if biome == Biome::ICE_SHEET && tile.ice <= params.sim.ice_thickness_of_ice_sheet {
         return false;
     }
 
@@ -294,8 +338,8 @@ fn calc_tile_max_biomass_density(
         planet.map[p].fertility,
     );
     let max_by_humidity = linear_interpolation(&params.sim.max_biomass_humidity_table, humidity[p]);
-    let land_or_sea_factor = if planet.map[p].biome.is_land() {
-        1.0
+    let land_or_sea_factor = if planet.map[p].biome.is_land(params) {
+        linear_interpolation(&params.sim.max_biomass_uv_table, planet.tile_uv(p))
     } else {
         params.sim.sea_biomass_factor
     };
@@ -0,0 +1,181 @@
+use super::{atmo::CO2_CARBON_WEIGHT_RATIO, misc::linear_interpolation, *};
+use fnv::FnvHashMap;
+
+impl Planet {
+    /// Space buildings added to assets after the planet was created are treated as not built
+    pub fn space_building(&self, kind: SpaceBuildingKind) -> &Building {
+        const NOT_BUILT: Building = Building {
+            n: 0,
+            control: BuildingControlValue::AlwaysEnabled,
+        };
+        self.space_buildings.get(&kind).unwrap_or(&NOT_BUILT)
+    }
+
+    pub fn space_building_mut(&mut self, kind: SpaceBuildingKind) -> &mut Building {
+        self.space_buildings.entry(kind).or_default()
+    }
+
+    pub fn working_building_effects<'a>(
+        &self,
+        p: Coords,
+        params: &'a Params,
+    ) -> &'a [BuildingEffect] {
+        if let Some(structure) = &self.map[p].structure {
+            params
+                .structures
+                .get(&structure.kind())
+                .map_or(&[], |s| &s.building.effects)
+        } else {
+            &[]
+        }
+    }
+}
+
+pub fn update(planet: &mut Planet, sim: &mut Sim, params: &Params) {
+    update_working_buildings(planet, &mut sim.working_buildings, params);
+
+    for (&kind, &n) in &sim.working_buildings {
+        if n == 0 {
+            continue;
+        }
+        let attrs = params.building_attrs(kind);
+        let control_value = if let BuildingKind::Space(kind) = kind {
+            Some(planet.space_building(kind).control)
+        } else {
+            None
+        };
+        if attrs.power > 0.0 {
+            planet.res.power += attrs.power * n as f32;
+        } else {
+            planet.res.used_power += -attrs.power * n as f32;
+        }
+
+        for effect in &attrs.effects {
+            match effect {
+                BuildingEffect::ProduceMaterial { mass } => {
+                    planet.res.diff_material += mass * n as f32;
+                }
+                BuildingEffect::ProduceGenePoint { value } => {
+                    planet.res.diff_gene_point += value * n as f32;
+                }
+                BuildingEffect::AdjustSolarPower { min, max } => {
+                    if let Some(BuildingControlValue::IncreaseRate(rate)) = control_value {
+                        let rate = rate.clamp(*min, *max);
+                        planet.state.solar_power_multiplier += (rate as f32) / 100.0;
+                    }
+                }
+                _ => (),
+            }
+        }
+    }
+}
+
+pub fn advance(planet: &mut Planet, sim: &mut Sim, params: &Params) {
+    for (&kind, &n) in &sim.working_buildings {
+        if n == 0 {
+            continue;
+        }
+        for effect in &params.building_attrs(kind).effects {
+            advance_building_effect(planet, effect, n, params);
+        }
+    }
+
+    for p in planet.map.iter_idx() {
+        process_building_on_tile(planet, p, sim, params);
+    }
+}
+
+fn advance_building_effect(planet: &mut Planet, effect: &BuildingEffect, n: u32, params: &Params) {
+    match effect {
+        BuildingEffect::RemoveAtmo {
+            mass,
+            efficiency_table,
+        } => {
+            let efficiency = linear_interpolation(efficiency_table, planet.atmo.atm());
+            planet
+                .atmo
+                .remove_atmo(*mass as f64 * n as f64 * efficiency as f64);
+            planet.atmo.ozone_damage = (planet.atmo.ozone_damage
+                + params.sim.ion_irradiation_ozone_damage * n as f32 * efficiency)
+                .min(1.0);
+        }
+        BuildingEffect::SprayToAtmo {
+            kind,
+            mass,
+            limit_atm,
+        } => {
+            if limit_atm.is_none_or(|limit_atm| planet.atmo.partial_pressure(*kind) < limit_atm) {
+                planet.atmo.add(*kind, mass * n as f32);
+            }
+        }
+        BuildingEffect::AddWater { value } => {
+            planet.water.water_volume += value;
+        }
+        BuildingEffect::MaterialIncome {
+            mass,
+            interval_cycles,
+        } => {
+            if planet.cycles % (*interval_cycles).max(1) == 0 {
+                planet.res.add_material(mass * n as f32);
+            }
+        }
+        BuildingEffect::RemoveAerosol { ratio } => {
+            let remaining = (1.0 - ratio).clamp(0.0, 1.0).powi(n as i32);
+            for tile in planet.map.iter_mut() {
+                tile.aerosol *= remaining;
+            }
+        }
+        _ => (),
+    }
+}
+
+fn process_building_on_tile(planet: &mut Planet, p: Coords, sim: &mut Sim, params: &Params) {
+    for effect in planet.working_building_effects(p, params) {
+        let BuildingEffect::CaptureCarbonDioxide { mass, limit_atm } = effect else {
+            continue;
+        };
+        // Remove co2 from atmosphere, and add buried carbon to one near tile.
+        let co2_mass_to_remove =
+            (planet.atmo.mass(GasKind::CarbonDioxide) / planet.atmo.total_mass()) * mass;
+        let carbon_mass = co2_mass_to_remove / CO2_CARBON_WEIGHT_RATIO;
+        planet.atmo.remove_carbon(carbon_mass);
+
+        if planet.atmo.partial_pressure(GasKind::Oxygen) < *limit_atm {
+            planet
+                .atmo
+                .add(GasKind::Oxygen, co2_mass_to_remove - carbon_mass);
+        }
+
+        loop {
+            let p_target = p + (sim.rng.random_range(-3..=3), sim.rng.random_range(-3..=3));
+            if planet.map.in_range(p_target) {
+                planet.map[p_target].buried_carbon += carbon_mass;
+                break;
+            }
+        }
+    }
+}
+
+fn update_working_buildings(
+    planet: &Planet,
+    working_buildings: &mut FnvHashMap<BuildingKind, u32>,
+    params: &Params,
+) {
+    working_buildings.clear();
+
+    for &kind in params.space_buildings.keys() {
+        let n = planet.space_building(kind).enabled();
+        working_buildings.insert(BuildingKind::Space(kind), n);
+    }
+
+    for p in planet.map.iter_idx() {
+        if let Some(structure) = &planet.map[p].structure {
+            let kind = structure.kind();
+            if kind != StructureKind::SETTLEMENT {
+                *working_buildings
+                    .entry(BuildingKind::Structure(kind))
+                    .or_insert(0) += 1;
+            }
+        }
+    }
+}
@@ -0,0 +1,82 @@
+use super::atmo::CO2_CARBON_WEIGHT_RATIO;
+use super::misc::linear_interpolation;
+use super::*;
+
+/// Carbon exchange between the atmosphere, the ocean and the land surface
+pub fn sim_carbon_cycle(planet: &mut Planet, sim: &mut Sim, params: &Params) {
+    silicate_weathering(planet, sim, params);
+    ocean_carbon_exchange(planet, sim, params);
+
+    // Dissolved carbon precipitates as carbonate sediments on the sea floor
+    let deposited = planet.water.dissolved_carbon * params.sim.carbonate_deposition_rate;
+    planet.water.dissolved_carbon -= deposited;
+    planet.water.sediment_carbon += deposited;
+
+    // Sediments subducted into the mantle return to the atmosphere by volcanic outgassing
+    let outgassed = planet.water.sediment_carbon * params.sim.carbonate_outgassing_rate;
+    planet.water.sediment_carbon -= outgassed;
+    planet
+        .atmo
+        .add(GasKind::CarbonDioxide, outgassed * CO2_CARBON_WEIGHT_RATIO);
+}
+
+/// Rock weathering by rainfall consumes CO2, and the carbon flows into the ocean as bicarbonate
+fn silicate_weathering(planet: &mut Planet, sim: &Sim, params: &Params) {
+    let co2_factor = linear_interpolation(
+        &params.sim.weathering_co2_table,
+        planet.atmo.partial_pressure(GasKind::CarbonDioxide),
+    );
+
+    let mut sum_weathering = 0.0;
+    for p in planet.map.iter_idx() {
+        let tile = &planet.map[p];
+        if !tile.biome.is_land(params) || tile.ice > params.sim.ice_thickness_of_ice_sheet {
+            continue;
+        }
+        let temp_factor = linear_interpolation(&params.sim.weathering_temp_table, tile.temp);
+        sum_weathering += (params.sim.silicate_weathering_rate
+            * sim.tile_area
+            * tile.rainfall
+            * temp_factor
+            * co2_factor) as f64;
+    }
+
+    let available = planet.atmo.mass(GasKind::CarbonDioxide) / CO2_CARBON_WEIGHT_RATIO;
+    let weathering = (sum_weathering as f32).min(available);
+    planet.atmo.add(
+        GasKind::CarbonDioxide,
+        -weathering * CO2_CARBON_WEIGHT_RATIO,
+    );
+    planet.water.dissolved_carbon += weathering;
+    planet.water.weathering_rate = weathering;
+}
+
+/// The ocean absorbs CO2 until its equilibrium, which decreases as the sea gets warmer
+fn ocean_carbon_exchange(planet: &mut Planet, sim: &Sim, params: &Params) {
+    let equilibrium = ocean_carbon_equilibrium(planet, params);
+
+    // The ocean is assumed to be in equilibrium with the atmosphere before start
+    if sim.before_start {
+        planet.water.dissolved_carbon = equilibrium;
+        return;
+    }
+
+    let available = planet.atmo.mass(GasKind::CarbonDioxide) / CO2_CARBON_WEIGHT_RATIO;
+    let absorbed = (params.sim.ocean_carbon_exchange_rate
+        * (equilibrium - planet.water.dissolved_carbon))
+        .min(available)
+        .max(-planet.water.dissolved_carbon);
+    planet
+        .atmo
+        .add(GasKind::CarbonDioxide, -absorbed * CO2_CARBON_WEIGHT_RATIO);
+    planet.water.dissolved_carbon += absorbed;
+}
+
+/// Dissolved carbon mass in equilibrium with the atmosphere [Mt]
+pub fn ocean_carbon_equilibrium(planet: &Planet, params: &Params) -> f32 {
+    linear_interpolation(
+        &params.sim.ocean_carbon_solubility_table,
+        planet.stat.average_sea_temp,
+    ) * planet.water.sea_water_volume()
+        * planet.atmo.partial_pressure(GasKind::CarbonDioxide)
+}
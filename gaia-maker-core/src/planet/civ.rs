@@ -20,11 +20,11 @@ pub fn sim_civs(planet: &mut Planet, sim: &mut Sim, params: &Params) {
             continue;
         };
         let animal_id = settlement.id;
-        let animal_attr = &params.animals[&animal_id];
+        let animal_attr = &super::species::detached_animal_attr(planet, &animal_id, params);
         let cr = sim.settlement_cr[p];
 
         // Delete settlement if the biome is unhabitable for the animal
-        if !animal_attr.habitat.match_biome(planet.map[p].biome) {
+        if !animal_attr.habitat.match_biome(planet.map[p].biome, params) {
             planet.map[p].structure = None;
             continue;
         }
@@ -227,7 +227,7 @@ fn spread_settlement(
         let Some(q) = sim.convert_p_cyclic(p + *d) else {
             continue;
         };
-        if !animal_attr.habitat.match_biome(planet.map[q].biome)
+        if !animal_attr.habitat.match_biome(planet.map[q].biome, params)
             || settlement_blocked_by_tile_event(&planet.map[q].tile_events)
         {
             continue;
@@ -369,7 +369,7 @@ fn spawn_vehicles(planet: &mut Planet, sim: &mut Sim, params: &Params) {
                     }
                 }
             };
-            if planet.map[p_adj].biome == Biome::Ocean && planet.map[p].biome.is_land() {
+            if planet.map[p_adj].biome.is_open_sea(params) && planet.map[p].biome.is_land(params) {
                 let kind = if settlement.age >= CivilizationAge::Atomic {
                     if sim.rng.random_bool(0.4) {
                         VehicleKind::AirPlane
@@ -483,12 +483,21 @@ impl Settlement {
     }
 }
 
+static DEFAULT_CIV_NAME: OnceLock<fn(AnimalId) -> String> = OnceLock::new();
+
+/// Set the function to get civilization names used if not named by the player
+pub fn set_default_civ_name_fn(f: fn(AnimalId) -> String) {
+    let _ = DEFAULT_CIV_NAME.set(f);
+}
+
 pub fn civ_name(civs: &Civs, id: AnimalId) -> String {
     if let Some(civ) = civs.get(&id) {
         if let Some(name) = &civ.name {
             name.into()
+        } else if let Some(f) = DEFAULT_CIV_NAME.get() {
+            f(id)
         } else {
-            t!("civ", id)
+            id.to_string()
         }
     } else {
         id.to_string()
@@ -42,8 +42,14 @@ pub fn update_civ_energy(planet: &Planet, sim: &mut Sim, params: &Params) {
             } else {
                 0.0
             };
+        let river = if planet.is_river(p, params) {
+            params.sim.river_hydro * planet.map[p].river_flow
+        } else {
+            0.0
+        };
         sim.energy_hydro_geothermal[p] =
-            linear_interpolation(&params.sim.table_rainfall_hydro, planet.map[p].rainfall)
+            (linear_interpolation(&params.sim.table_rainfall_hydro, planet.map[p].rainfall)
+                + river)
                 * sim.tile_area
                 + geothermal;
     }
@@ -67,10 +73,11 @@ pub fn update_civ_energy(planet: &Planet, sim: &mut Sim, params: &Params) {
             }
         }
 
-        if matches!(planet.map[p].structure, Some(Structure::GiftTower)) {
-            let attrs = params.building_attrs(StructureKind::GiftTower);
-            if let Some(BuildingEffect::SupplyEnergy { value }) = attrs.effect {
-                sum_values.gift_supply += value;
+        if let Some(Structure::Building(kind)) = planet.map[p].structure {
+            for effect in &params.building_attrs(kind).effects {
+                if let BuildingEffect::SupplyEnergy { value } = effect {
+                    sum_values.gift_supply += value;
+                }
             }
         }
     }
@@ -223,7 +230,7 @@ pub fn process_settlement_energy(
         }
     }
     consume[EnergySource::Biomass as usize] += remaining;
-    let biomass_eff_factor = if planet.map[p].biome.is_land() {
+    let biomass_eff_factor = if planet.map[p].biome.is_land(params) {
         linear_interpolation(
             &params.sim.biomass_energy_efficiency_density_factor_table,
             planet.map[p].biomass,
@@ -298,6 +305,8 @@ pub fn consume_buried_carbon(planet: &mut Planet, sim: &mut Sim, params: &Params
             planet.map[*p].buried_carbon = (planet.map[*p].buried_carbon - consume_mass).max(0.0);
         }
 
-        planet.atmo.release_carbon(mass);
+        let leaked_mass = mass * params.sim.fossil_fuel_methane_leak_ratio;
+        planet.atmo.release_carbon(mass - leaked_mass);
+        planet.atmo.release_carbon_as_methane(leaked_mass);
     }
 }
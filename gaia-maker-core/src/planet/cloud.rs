@@ -0,0 +1,89 @@
+use super::atmo::AEROSOL_EQUILIBRIUM_TARGET;
+use super::misc::linear_interpolation;
+use super::wind::advection;
+use super::*;
+use geom::Direction;
+use rayon::prelude::*;
+
+/// Aerosol spreads from its source tiles, and clouds form by local humidity and aerosol
+pub fn sim_aerosol_and_cloud(planet: &mut Planet, sim: &mut Sim, params: &Params) {
+    let size = planet.map.size();
+    let coords_converter = sim.coords_converter();
+
+    for p in planet.map.iter_idx() {
+        sim.aerosol[p] = planet.map[p].aerosol;
+    }
+
+    let base_supply = (1.0 - params.sim.aerosol_remaining_rate) * AEROSOL_EQUILIBRIUM_TARGET;
+    let par_iter = sim.aerosol_new.par_iter_mut().enumerate();
+    par_iter.for_each(|(i, aerosol_new)| {
+        let p = Coords::from_index_size(i, size);
+        let adjacent_tile_flow: f32 = Direction::FOUR_DIRS
+            .into_iter()
+            .map(|dir| {
+                if let Some(adjacent_tile) = coords_converter.conv(p + dir.as_coords()) {
+                    0.5 * params.sim.aerosol_diffusion_factor
+                        * (sim.aerosol[adjacent_tile] - sim.aerosol[p])
+                } else {
+                    0.0
+                }
+            })
+            .sum();
+        let wind_flow = advection(&sim.wind, &sim.aerosol, p, &coords_converter, |_, _| {
+            params.sim.wind_aerosol_advection_factor
+        });
+        *aerosol_new = (sim.aerosol[p] + adjacent_tile_flow + wind_flow + base_supply).max(0.0)
+            * params.sim.aerosol_remaining_rate;
+    });
+
+    let mut sum_aerosol = 0.0;
+    let mut sum_cloud = 0.0;
+    for p in planet.map.iter_idx() {
+        let tile = &mut planet.map[p];
+        tile.aerosol = sim.aerosol_new[p];
+        tile.cloud = linear_interpolation(&params.sim.aerosol_cloud_table, tile.aerosol)
+            * linear_interpolation(&params.sim.cloud_humidity_table, sim.humidity[p]);
+        sum_aerosol += tile.aerosol as f64;
+        sum_cloud += tile.cloud as f64;
+    }
+
+    let n_tiles = planet.n_tile() as f64;
+    planet.atmo.aerosol = (sum_aerosol / n_tiles) as f32;
+    planet.atmo.cloud_amount = (sum_cloud / n_tiles) as f32;
+}
+
+/// Emit aerosol spread over the tiles around `p`. `amount` is the increase of the average
+/// aerosol of the planet, so that the total emission does not depend on the map size
+pub fn emit_aerosol(planet: &mut Planet, sim: &Sim, p: Coords, amount: f32, params: &Params) {
+    let coords_converter = sim.coords_converter();
+    let shape = geom::Shape::Circle {
+        center: p,
+        radius: params.event.aerosol_emission_radius,
+    };
+    let tiles: Vec<_> = shape
+        .iter()
+        .into_iter()
+        .filter_map(|p| coords_converter.conv(p))
+        .collect();
+    let amount_per_tile = amount * sim.n_tiles as f32 / tiles.len() as f32;
+    for p in tiles {
+        planet.map[p].aerosol += amount_per_tile;
+    }
+}
+
+impl Planet {
+    /// Albedo by clouds over the tile
+    pub fn tile_cloud_albedo(&self, p: Coords, params: &Params) -> f32 {
+        linear_interpolation(&params.sim.cloud_albedo_table, self.map[p].cloud)
+    }
+
+    /// Average cloud albedo of the planet
+    pub fn cloud_albedo(&self, params: &Params) -> f32 {
+        let sum: f32 = self
+            .map
+            .iter_idx()
+            .map(|p| self.tile_cloud_albedo(p, params))
+            .sum();
+        sum / self.n_tile() as f32
+    }
+}
@@ -10,7 +10,16 @@ static POS_FOR_LOG: LazyLock<RwLock<Option<Coords>>> = LazyLock::new(RwLock::def
 static TILE_LOGS: LazyLock<RwLock<BTreeMap<&'static str, String>>> = LazyLock::new(RwLock::default);
 
 pub fn clear_logs(p: Option<Coords>) {
+    set_pos_for_log(p);
+    clear_tile_logs();
+}
+
+/// Set the tile whose logs are recorded
+pub fn set_pos_for_log(p: Option<Coords>) {
     *POS_FOR_LOG.write().unwrap() = p;
+}
+
+pub fn clear_tile_logs() {
     TILE_LOGS.write().unwrap().clear();
 }
 
@@ -8,6 +8,8 @@ use serde_repr::{Deserialize_repr, Serialize_repr};
 use serde_with::{DisplayFromStr, Same, serde_as};
 use strum::{AsRefStr, Display, EnumDiscriminants, EnumIter, EnumString, IntoEnumIterator};
 
+use super::id::IdStr;
+use super::requirement::Requirement;
 use super::serde_with_types::*;
 
 pub const TILE_SIZE: f32 = 48.0;
@@ -32,6 +34,22 @@ pub struct Basics {
     pub solar_constant: f32,
     /// Geothermal power from the planet core [W]
     pub geothermal_power: f32,
+    /// Axial tilt [deg]
+    #[serde(default)]
+    pub axial_tilt: f32,
+    /// Orbital eccentricity
+    #[serde(default)]
+    pub eccentricity: f32,
+    /// The planet rotates in the opposite direction to its orbit
+    #[serde(default)]
+    pub retrograde_rotation: bool,
+    /// Strength of the magnetosphere relative to the present Earth
+    #[serde(default = "magnetosphere_default")]
+    pub magnetosphere: f32,
+}
+
+fn magnetosphere_default() -> f32 {
+    1.0
 }
 
 #[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
@@ -65,47 +83,132 @@ fn control_value_default() -> i16 {
     100
 }
 
-#[derive(Clone, Copy, PartialEq, Eq, Hash, Default, Debug)]
-#[derive(Serialize_repr, Deserialize_repr, EnumString, Display, EnumIter, AsRefStr)]
-#[strum(serialize_all = "kebab-case")]
-#[repr(u8)]
-pub enum Biome {
-    #[default]
-    // Barren
-    Rock = 1,
-    IceSheet,
-    Desert,
-    // Water
-    Ocean = 21,
-    SeaIce,
-    // Grassland
-    Grassland = 41,
-    Tundra,
-    // Forest
-    BorealForest = 61,
-    TemperateForest,
-    TropicalRainforest,
-}
+/// Biome id. Biomes are registered in `biomes/list.biomes.ron` and referenced by this id
+#[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Debug)]
+pub struct Biome(IdStr);
 
 impl Biome {
-    pub fn is_land(&self) -> bool {
-        !self.is_sea()
+    pub const ROCK: Biome = Biome::new("rock");
+    pub const ICE_SHEET: Biome = Biome::new("ice-sheet");
+    pub const DESERT: Biome = Biome::new("desert");
+    pub const OCEAN: Biome = Biome::new("ocean");
+    pub const SEA_ICE: Biome = Biome::new("sea-ice");
+
+    pub const fn new(id: &str) -> Self {
+        Biome(IdStr::new(id))
+    }
+
+    pub fn is_land(&self, params: &Params) -> bool {
+        params.biomes[self].category == BiomeCategory::Land
     }
 
-    pub fn is_sea(&self) -> bool {
-        matches!(*self, Biome::Ocean | Biome::SeaIce)
+    pub fn is_sea(&self, params: &Params) -> bool {
+        params.biomes[self].category == BiomeCategory::Sea
+    }
+
+    /// Sea not covered by ice
+    pub fn is_open_sea(&self, params: &Params) -> bool {
+        let attrs = &params.biomes[self];
+        attrs.category == BiomeCategory::Sea && !attrs.frozen
+    }
+
+    /// Biome ids used by old save data
+    pub fn from_legacy_number(n: u64) -> Option<Self> {
+        let id = match n {
+            1 => "rock",
+            2 => "ice-sheet",
+            3 => "desert",
+            21 => "ocean",
+            22 => "sea-ice",
+            41 => "grassland",
+            42 => "tundra",
+            61 => "boreal-forest",
+            62 => "temperate-forest",
+            63 => "tropical-rainforest",
+            _ => return None,
+        };
+        Some(Biome::new(id))
+    }
+}
+
+impl Default for Biome {
+    fn default() -> Self {
+        Biome::ROCK
+    }
+}
+
+impl AsRef<str> for Biome {
+    fn as_ref(&self) -> &str {
+        self.0.as_str()
     }
 }
 
+impl std::fmt::Display for Biome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl std::str::FromStr for Biome {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.parse().map(Biome)
+    }
+}
+
+impl Serialize for Biome {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.0.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Biome {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct BiomeVisitor;
+
+        impl serde::de::Visitor<'_> for BiomeVisitor {
+            type Value = Biome;
+
+            fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                f.write_str("biome id")
+            }
+
+            fn visit_str<E: serde::de::Error>(self, v: &str) -> Result<Self::Value, E> {
+                v.parse().map_err(E::custom)
+            }
+
+            fn visit_u64<E: serde::de::Error>(self, v: u64) -> Result<Self::Value, E> {
+                // Unknown numbers are kept as an unregistered id, and replaced at migration
+                Ok(Biome::from_legacy_number(v).unwrap_or(Biome::new("unknown")))
+            }
+        }
+
+        deserializer.deserialize_any(BiomeVisitor)
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub enum BiomeCategory {
+    Land,
+    Sea,
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct BiomeAttrs {
+    pub category: BiomeCategory,
     pub z: f32,
     pub albedo: f32,
     pub revaporization_ratio: f32,
     pub priority: u32,
     pub mean_transition_time: f32,
     pub requirements: BiomeRequirements,
+    /// Covered by ice
+    #[serde(default)]
+    pub frozen: bool,
     pub color: [u8; 3],
+    /// Texture path in the assets directory
+    pub texture: String,
 }
 
 #[serde_as]
@@ -129,6 +232,16 @@ pub struct StructureAttrs {
     pub columns: usize,
     pub rows: usize,
     pub building: BuildingAttrs,
+    /// Texture path in the assets directory
+    pub texture: String,
+    /// Color in the structure map
+    #[serde(default)]
+    pub color: [u8; 3],
+    #[serde(default)]
+    pub placement: StructurePlacement,
+    /// Requirement to unlock building by the player
+    #[serde(default, with = "serde_with::rust::unwrap_or_skip")]
+    pub unlock: Option<Requirement>,
 }
 
 impl AsRef<BuildingAttrs> for StructureAttrs {
@@ -137,31 +250,143 @@ impl AsRef<BuildingAttrs> for StructureAttrs {
     }
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize, EnumDiscriminants)]
-#[strum_discriminants(name(StructureKind))]
-#[strum_discriminants(derive(PartialOrd, Ord, Hash, Serialize, Deserialize))]
-#[strum_discriminants(derive(EnumIter, AsRefStr, Display))]
-#[strum_discriminants(serde(rename_all = "snake_case"))]
-#[strum_discriminants(strum(serialize_all = "kebab-case"))]
+/// Rules for tiles that a structure can be placed on
+#[derive(Clone, Default, Debug, Serialize, Deserialize)]
+pub struct StructurePlacement {
+    #[serde(default)]
+    pub surface: PlacementSurface,
+    /// All of them must be satisfied by the adjacent tiles
+    #[serde(default)]
+    pub adjacent: Vec<AdjacencyRule>,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Default, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PlacementSurface {
+    #[default]
+    Any,
+    Land,
+    Sea,
+}
+
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AdjacencyRule {
+    /// At least one adjacent tile is sea
+    Sea,
+    /// At least one adjacent tile is land
+    Land,
+    /// At least one adjacent tile has one of the structures
+    Structure(Vec<StructureKind>),
+    /// No adjacent tile has one of the structures
+    NoStructure(Vec<StructureKind>),
+}
+
+/// Structure id. Structures are registered in `structures/list.structures.ron`
+#[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Debug)]
+pub struct StructureKind(IdStr);
+
+impl StructureKind {
+    pub const SETTLEMENT: StructureKind = StructureKind::new("settlement");
+
+    pub const fn new(id: &str) -> Self {
+        StructureKind(IdStr::new(id))
+    }
+
+    pub fn buildable_by_player(self) -> bool {
+        self != Self::SETTLEMENT
+    }
+}
+
+impl AsRef<str> for StructureKind {
+    fn as_ref(&self) -> &str {
+        self.0.as_str()
+    }
+}
+
+impl std::fmt::Display for StructureKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl std::str::FromStr for StructureKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.parse().map(StructureKind)
+    }
+}
+
+impl Serialize for StructureKind {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.0.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for StructureKind {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        // Old save data uses snake case names
+        let s = <std::borrow::Cow<'de, str>>::deserialize(deserializer)?;
+        s.replace('_', "-").parse().map_err(serde::de::Error::custom)
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(from = "StructureRepr")]
 pub enum Structure {
+    /// Structure built by the player
+    Building(StructureKind),
+    Settlement(Settlement),
+}
+
+/// Representation of `Structure` including variants in old save data
+#[derive(Deserialize)]
+enum StructureRepr {
+    Building(StructureKind),
+    Settlement(Settlement),
     OxygenGenerator,
     Rainmaker,
     FertilizationPlant,
     Heater,
     CarbonCapturer,
     GiftTower,
-    Settlement(Settlement),
+}
+
+impl From<StructureRepr> for Structure {
+    fn from(repr: StructureRepr) -> Self {
+        let id = match repr {
+            StructureRepr::Building(kind) => return Structure::Building(kind),
+            StructureRepr::Settlement(settlement) => return Structure::Settlement(settlement),
+            StructureRepr::OxygenGenerator => "oxygen-generator",
+            StructureRepr::Rainmaker => "rainmaker",
+            StructureRepr::FertilizationPlant => "fertilization-plant",
+            StructureRepr::Heater => "heater",
+            StructureRepr::CarbonCapturer => "carbon-capturer",
+            StructureRepr::GiftTower => "gift-tower",
+        };
+        Structure::Building(StructureKind::new(id))
+    }
 }
 
 impl Structure {
     pub fn kind(&self) -> StructureKind {
-        self.into()
+        match self {
+            Structure::Building(kind) => *kind,
+            Structure::Settlement(_) => StructureKind::SETTLEMENT,
+        }
+    }
+
+    /// Create a structure built by the player
+    pub fn new_building(kind: StructureKind) -> Self {
+        assert!(kind.buildable_by_player());
+        Structure::Building(kind)
     }
 }
 
-impl StructureKind {
-    pub fn buildable_by_player(self) -> bool {
-        !matches!(self, Self::Settlement)
+impl From<&Structure> for StructureKind {
+    fn from(structure: &Structure) -> Self {
+        structure.kind()
     }
 }
 
@@ -251,12 +476,30 @@ pub struct AnimalAttr {
     pub color: [u8; 3],
     #[serde(default)]
     pub evolve_from: Vec<(AnimalId, f32)>,
+    /// Food sources. Herbivores if not given
+    #[serde(default = "animal_diet_default")]
+    pub diet: Vec<Diet>,
 }
 
 fn animal_ratio_attr_default() -> f32 {
     1.0
 }
 
+fn animal_diet_default() -> Vec<Diet> {
+    vec![Diet::Biomass]
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Diet {
+    /// Plants on land or plankton in the sea
+    Biomass,
+    /// Animals of the species
+    Animal(AnimalId),
+    /// Animals of the size living in the same habitat
+    Size(AnimalSize),
+}
+
 #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Serialize, Deserialize, AsRefStr)]
 #[serde(rename_all = "kebab-case")]
 #[strum(serialize_all = "kebab-case")]
@@ -418,6 +661,8 @@ pub enum GasKind {
     Nitrogen,
     CarbonDioxide,
     Argon,
+    Methane,
+    WaterVapor,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -428,8 +673,9 @@ pub struct BuildingAttrs {
     pub cost: f32,
     #[serde(default, with = "serde_with::rust::unwrap_or_skip")]
     pub build_max: Option<u32>,
-    #[serde(default, with = "serde_with::rust::unwrap_or_skip")]
-    pub effect: Option<BuildingEffect>,
+    /// Effects applied for each working building
+    #[serde(default)]
+    pub effects: Vec<BuildingEffect>,
     #[serde(default)]
     pub control: BuildingControl,
 }
@@ -444,19 +690,91 @@ pub enum BuildingControl {
     IncreaseRate,
 }
 
-#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
-#[derive(Serialize, Deserialize, EnumString, EnumIter, AsRefStr)]
+/// Space building id. Space buildings are loaded from `space_buildings/*.space_building.ron`
+#[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Debug)]
+pub struct SpaceBuildingKind(IdStr);
+
+impl SpaceBuildingKind {
+    pub const fn new(id: &str) -> Self {
+        SpaceBuildingKind(IdStr::new(id))
+    }
+}
+
+impl AsRef<str> for SpaceBuildingKind {
+    fn as_ref(&self) -> &str {
+        self.0.as_str()
+    }
+}
+
+impl std::fmt::Display for SpaceBuildingKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl std::str::FromStr for SpaceBuildingKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.parse().map(SpaceBuildingKind)
+    }
+}
+
+impl Serialize for SpaceBuildingKind {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.0.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for SpaceBuildingKind {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        // Old save data uses snake case names
+        let s = <std::borrow::Cow<'de, str>>::deserialize(deserializer)?;
+        s.replace('_', "-").parse().map_err(serde::de::Error::custom)
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SpaceBuildingAttrs {
+    /// Position in the space building list
+    pub order: u32,
+    /// Background drawn behind the buildings in the list
+    #[serde(default)]
+    pub background: SpaceBuildingBackground,
+    pub building: BuildingAttrs,
+}
+
+impl BuildingAttrs {
+    /// Range of the control rate [%] of the building
+    pub fn control_rate_range(&self) -> std::ops::RangeInclusive<i32> {
+        self.effects
+            .iter()
+            .find_map(|effect| {
+                if let BuildingEffect::AdjustSolarPower { min, max } = effect {
+                    Some(*min..=*max)
+                } else {
+                    None
+                }
+            })
+            .unwrap_or(-100..=100)
+    }
+}
+
+impl AsRef<BuildingAttrs> for SpaceBuildingAttrs {
+    fn as_ref(&self) -> &BuildingAttrs {
+        &self.building
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Default, Debug, Serialize, Deserialize, AsRefStr)]
 #[serde(rename_all = "snake_case")]
 #[strum(serialize_all = "kebab-case")]
-pub enum SpaceBuildingKind {
-    FusionReactor,
-    AsteroidMiningStation,
-    DysonSwarmUnit,
-    OrbitalMirror,
-    IceImporter,
-    NitrogenImporter,
-    CarbonImporter,
-    IonIrradiator,
+pub enum SpaceBuildingBackground {
+    #[default]
+    Space,
+    Star,
+    Planet,
+    PlanetLeft,
 }
 
 #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Serialize, Deserialize)]
@@ -483,15 +801,33 @@ pub enum BuildingEffect {
     ProduceMaterial {
         mass: f32,
     },
-    AdjustSolarPower,
+    /// Add material every `interval_cycles` cycles
+    MaterialIncome {
+        mass: f32,
+        interval_cycles: u64,
+    },
+    ProduceGenePoint {
+        value: f32,
+    },
+    /// Adjust the solar power multiplier by the control rate in the range [%]
+    AdjustSolarPower {
+        min: i32,
+        max: i32,
+    },
     RemoveAtmo {
         mass: f32,
         efficiency_table: Vec<(f32, f32)>,
     },
+    /// Add gas to the atmosphere. Not limited if `limit_atm` is not given
     SprayToAtmo {
         kind: GasKind,
         mass: f32,
-        limit_atm: f32,
+        #[serde(default, with = "serde_with::rust::unwrap_or_skip")]
+        limit_atm: Option<f32>,
+    },
+    /// Remove the ratio of aerosol on every tile
+    RemoveAerosol {
+        ratio: f32,
     },
     AddWater {
         value: f32,
@@ -580,7 +916,8 @@ pub struct Params {
     pub biomes: FnvHashMap<Biome, BiomeAttrs>,
     #[serde(skip)]
     pub structures: FnvHashMap<StructureKind, StructureAttrs>,
-    pub space_buildings: FnvHashMap<SpaceBuildingKind, BuildingAttrs>,
+    #[serde(skip)]
+    pub space_buildings: FnvHashMap<SpaceBuildingKind, SpaceBuildingAttrs>,
     #[serde(skip)]
     pub start_planets: Vec<StartPlanet>,
     #[serde(skip)]
@@ -592,8 +929,59 @@ impl Params {
     pub fn building_attrs<T: Into<BuildingKind>>(&self, kind: T) -> &BuildingAttrs {
         match kind.into() {
             BuildingKind::Structure(kind) => &self.structures[&kind].building,
-            BuildingKind::Space(kind) => &self.space_buildings[&kind],
+            BuildingKind::Space(kind) => &self.space_buildings[&kind].building,
+        }
+    }
+
+    /// Loaded space buildings in the list order
+    pub fn space_building_list(&self) -> Vec<SpaceBuildingKind> {
+        let mut list: Vec<SpaceBuildingKind> = self.space_buildings.keys().copied().collect();
+        list.sort_by_key(|kind| (self.space_buildings[kind].order, *kind));
+        list
+    }
+
+    /// Registered structures ordered by id
+    pub fn structure_list(&self) -> Vec<StructureKind> {
+        let mut list: Vec<StructureKind> = self.structures.keys().copied().collect();
+        list.sort();
+        list
+    }
+
+    /// Registered biomes ordered by priority
+    pub fn biome_list(&self) -> Vec<Biome> {
+        let mut list: Vec<Biome> = self.biomes.keys().copied().collect();
+        list.sort_by_key(|biome| (self.biomes[biome].priority, *biome));
+        list
+    }
+
+    /// Check that all biomes referenced by the simulation and parameters are registered
+    pub fn check_biome_ids(&self) -> Result<(), String> {
+        let check = |biome: &Biome, referrer: &dyn std::fmt::Display| {
+            if self.biomes.contains_key(biome) {
+                Ok(())
+            } else {
+                Err(format!("unknown biome \"{}\" in {}", biome, referrer))
+            }
+        };
+
+        for biome in [Biome::ROCK, Biome::OCEAN, Biome::SEA_ICE, Biome::ICE_SHEET] {
+            check(&biome, &"core biomes")?;
+        }
+        for (animal_id, attr) in &self.animals {
+            if let AnimalHabitat::Biomes(biomes) = &attr.habitat {
+                for biome in biomes {
+                    check(biome, &format!("habitat of animal {}", animal_id))?;
+                }
+            }
+        }
+        for (kind, attrs) in &self.structures {
+            if let Some(Requirement::BiomeTiles { biomes, .. }) = &attrs.unlock {
+                for biome in biomes {
+                    check(biome, &format!("unlock requirement of structure {}", kind))?;
+                }
+            }
         }
+        Ok(())
     }
 }
 
@@ -613,8 +1001,14 @@ pub struct StartParams {
     pub target_sea_area: Option<f32>,
     #[serde(default)]
     pub height_map: Vec<f32>,
-    #[serde(default, with = "serde_with::rust::unwrap_or_skip")]
+    #[serde(default, with = "super::serde_with_types::unwrap_or_unit")]
     pub initial_buried_carbon: Option<InitialBuriedCarbon>,
+    /// Random seed for map generation and simulation. Chosen randomly if not given
+    #[serde(default, with = "super::serde_with_types::unwrap_or_unit")]
+    pub seed: Option<u64>,
+    /// Material given at the start of the game
+    #[serde(default, with = "super::serde_with_types::unwrap_or_unit")]
+    pub material_at_start: Option<f32>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -645,10 +1039,20 @@ pub struct SimParams {
     pub max_deep_sea_layer_thickness: f32,
     /// Latitude and averaged insolation table
     pub latitude_insolation_table: Vec<(f32, f32)>,
+    /// The number of cycles in a year
+    pub cycles_per_year: u64,
     /// The ratio of tile air diffusion
     pub air_diffusion_factor: f32,
     /// The ratio of tile sea diffusion
     pub sea_diffusion_factor: f32,
+    /// The ratio of air heat carried by prevailing wind
+    pub wind_heat_advection_factor: f32,
+    /// The ratio of sea heat carried by ocean currents
+    pub ocean_current_heat_advection_factor: f32,
+    /// The number of loop to smooth ocean currents
+    pub n_loop_ocean_current_smoothing: usize,
+    /// The ratio of northward wind speed to eastward wind speed
+    pub meridional_wind_ratio: f32,
     /// The number of loop of atmosphere heat transfer calculation
     pub n_loop_atmo_heat_calc: usize,
     /// Albedo by cloud table
@@ -657,9 +1061,19 @@ pub struct SimParams {
     pub aerosol_remaining_rate: f32,
     /// Aerosol to cloud table
     pub aerosol_cloud_table: Vec<(f32, f32)>,
+    /// Cloud amount factor by tile humidity
+    pub cloud_humidity_table: Vec<(f32, f32)>,
+    /// Aerosol diffusion factor to adjacent tiles
+    pub aerosol_diffusion_factor: f32,
+    /// Aerosol advection factor by wind
+    pub wind_aerosol_advection_factor: f32,
     /// Greeh house effect table of CO2
     pub co2_green_house_effect_table: Vec<(f32, f32)>,
-    /// Greeh house effect table of cloud
+    /// Greeh house effect table of methane
+    pub methane_green_house_effect_table: Vec<(f32, f32)>,
+    /// Greeh house effect table of water vapor by its partial pressure [atm]
+    pub vapor_green_house_effect_table: Vec<(f32, f32)>,
+    /// Greeh house effect table of tile cloud
     pub cloud_green_house_effect_table: Vec<(f32, f32)>,
     /// Greeh house effect decrease by height at 1atm
     pub green_house_effect_height_decrease: f32,
@@ -669,11 +1083,25 @@ pub struct SimParams {
     pub vapor_diffusion_factor: f32,
     /// Coefficent to adjust vapor diffusion by height difference
     pub coeff_vapor_diffusion_adjust_by_h_diff: f32,
+    /// The ratio of vapor carried by prevailing wind
+    pub wind_vapor_advection_factor: f32,
+    /// Minimum flow to form a river [mm]
+    pub river_flow_threshold: f32,
+    /// Minimum inflow to a closed basin to form a lake [mm]
+    pub lake_flow_threshold: f32,
+    /// Humidity added to river tiles [mm]
+    pub river_humidity_bonus: f32,
     /// The ratio of vapor loss
     pub vapor_loss_ratio: f32,
     /// Vaporizaion from ocean tile - °C table
     #[serde_as(as = "Vec<(Celsius, Same)>")]
     pub ocean_vaporization_table: Vec<(f32, f32)>,
+    /// Ratio of atmospheric water vapor to the saturation vapor pressure
+    pub atmo_relative_humidity: f32,
+    /// The ratio of difference from the target vapor exchanged between the sea and atmosphere per cycle
+    pub atmo_vapor_exchange_rate: f32,
+    /// The ratio of difference from the target vapor evaporated per cycle while the ocean boils
+    pub ocean_boiling_rate: f32,
     /// Drying factors for humidity calculation (humidity = rainfall - factors.0 * (temperature + factors.1))
     pub drying_factors: (f32, f32),
     /// Max fertility table by temperature
@@ -703,6 +1131,8 @@ pub struct SimParams {
     pub max_biomass_humidity_table: Vec<(f32, f32)>,
     /// Max biomass by O2
     pub max_biomass_factor_o2_table: Vec<(f32, f32)>,
+    /// Max biomass of land tiles by surface UV
+    pub max_biomass_uv_table: Vec<(f32, f32)>,
     /// Max biomass by settlement population
     pub max_biomass_pop_table: Vec<(f32, f32)>,
     /// Max biomass by settlement population effect to adjacent tiles
@@ -723,6 +1153,46 @@ pub struct SimParams {
     pub biomass_to_buried_carbon_ratio_o2_table: Vec<(f32, f32)>,
     /// Table of decreased biomass to buried carbon ratio by carbon dioxide atm
     pub biomass_to_buried_carbon_ratio_co2_table: Vec<(f32, f32)>,
+    /// The ratio of carbon released as methane from decreased biomass
+    pub biomass_decay_methane_ratio: f32,
+    /// Minimum humidity of land tiles to emit methane as wetlands
+    pub wetland_humidity_threshold: f32,
+    /// Methane emission from wetlands per biomass carbon mass per cycle
+    pub wetland_methane_emission: f32,
+    /// Table of methane oxidation ratio per cycle by oxygen atm
+    pub methane_oxidation_o2_table: Vec<(f32, f32)>,
+    /// Dissolved carbon in equilibrium per sea water volume and CO2 atm by sea temperature [Mt/m^3]
+    pub ocean_carbon_solubility_table: Vec<(f32, f32)>,
+    /// The ratio of difference from the equilibrium exchanged between the ocean and atmosphere per cycle
+    pub ocean_carbon_exchange_rate: f32,
+    /// The ratio of dissolved carbon deposited as carbonate per cycle
+    pub carbonate_deposition_rate: f32,
+    /// The ratio of carbonate sediments released as CO2 by volcanic outgassing per cycle
+    pub carbonate_outgassing_rate: f32,
+    /// Carbon removed by weathering per rainfall [Mt/(m^2*mm)]
+    pub silicate_weathering_rate: f32,
+    /// Weathering speed factor by tile temperature
+    pub weathering_temp_table: Vec<(f32, f32)>,
+    /// Weathering speed factor by CO2 atm
+    pub weathering_co2_table: Vec<(f32, f32)>,
+    /// Exobase temperature of the default planet [K]
+    pub exobase_temp: f32,
+    /// Jeans parameter of hydrogen at the exobase of the default planet
+    pub jeans_parameter_hydrogen: f32,
+    /// Coefficent of thermal escape ratio per cycle
+    pub jeans_escape_coef: f32,
+    /// Escape ratio per cycle by stellar wind without magnetosphere
+    pub non_thermal_escape_rate: f32,
+    /// Factor of water escape by average air temperature [K]
+    pub water_escape_temp_table: Vec<(f32, f32)>,
+    /// Ozone column by oxygen atm [DU]
+    pub ozone_o2_table: Vec<(f32, f32)>,
+    /// Surface UV relative to the present Earth by ozone column [DU]
+    pub surface_uv_ozone_table: Vec<(f32, f32)>,
+    /// Recovery rate of damaged ozone layer per cycle
+    pub ozone_recovery_rate: f32,
+    /// Ozone layer damage by ion irradiation per building
+    pub ion_irradiation_ozone_damage: f32,
     /// Sea biomass factor compared to land
     pub sea_biomass_factor: f32,
     /// Required thickness of ice for ice sheet [m]
@@ -735,6 +1205,16 @@ pub struct SimParams {
     pub ice_thickness_limit_table: Vec<(f32, f32)>,
     /// Factor for adding ice height from rainfall [m/(rainfall)mm]
     pub fallen_snow_factor: f32,
+    /// Terrain simulation interval cycles
+    pub terrain_sim_interval_cycles: u64,
+    /// Coefficent of erosion by water flow
+    pub erosion_factor: f32,
+    /// The number of tectonic plates
+    pub n_tectonic_plates: u32,
+    /// Uplift at converging plate boundaries per terrain simulation [m]
+    pub tectonic_uplift: f32,
+    /// Height above sea level where tectonic uplift stops [m]
+    pub max_tectonic_height: f32,
     /// Biome transition probability before start simulation
     pub before_start_biome_transition_probability: f32,
     /// Animal simulation interval cycles
@@ -749,6 +1229,14 @@ pub struct SimParams {
     pub animal_cap_max_biomass: f32,
     /// Animal capacity effect by fertility in sea tiles
     pub animal_cap_max_fertility: f32,
+    /// Predator capacity effect by prey density in the surrounding tiles
+    pub animal_cap_max_prey: f32,
+    /// Capacity of carnivores by scavenging, used as the minimum without prey
+    pub animal_cap_scavenging: f32,
+    /// Prey decrease speed by predator density in the surrounding tiles
+    pub animal_predation_speed: f32,
+    /// Biomass density consumed by herbivores on land for each size [kg/m2]
+    pub animal_grazing: [f32; AnimalSize::LEN],
     /// Probability of animal moving
     pub animal_move_weight: f64,
     /// Weight to calculate animal congestion rate by other animals
@@ -765,12 +1253,28 @@ pub struct SimParams {
     pub animal_fission_overwrite_prob: f64,
     /// Animal livable oxygen range by size
     pub livable_oxygen_range: [(f32, f32); AnimalSize::LEN],
+    /// Land animal livability by surface UV
+    pub livable_uv_table: Vec<(f32, f32)>,
     /// Coefficent to calculate gene point income.
     pub coef_gene_point_income: f32,
     /// Needed evolution experience to evolve
     pub needed_evo_exp_to_evolve: f32,
     /// Base evolution probability
     pub base_evolution_prob: f32,
+    /// Probability that an isolated or stressed animal branches into a new species
+    pub species_branch_prob: f32,
+    /// Livability by temperature, oxygen and UV under which the environment is extreme
+    pub species_extreme_livability: f32,
+    /// Maximum mutation ratio of attributes of new species
+    pub species_mutation_ratio: f32,
+    /// Maximum shift of livable temperature range toward the environment [K]
+    pub species_temp_shift_max: f32,
+    /// Probability of habitat extension to a biome in the surrounding tiles
+    pub species_habitat_mutation_prob: f64,
+    /// Maximum number of generated species on a planet
+    pub species_max: usize,
+    /// Syllables of generated species names
+    pub species_name_syllables: Vec<String>,
     /// Base civilization probability
     pub base_civ_prob: f32,
     /// Civilization probability factor by animal size
@@ -835,6 +1339,8 @@ pub struct SimParams {
     pub tech_exp_evolution: [f32; CivilizationAge::LEN - 1],
     /// Rainfall to hydro energy source table [mm] - [GJ/m^2]
     pub table_rainfall_hydro: Vec<(f32, f32)>,
+    /// Hydro energy source per river flow [GJ/(m^2*mm)]
+    pub river_hydro: f32,
     /// Available geothermal ratio by civilization
     pub available_geothermal_ratio: f32,
     /// Solar constant to wind & solar energy source table [W/m^2] - [GJ/m^2]
@@ -847,6 +1353,8 @@ pub struct SimParams {
     pub max_depth_undersea_resource: f32,
     /// Fossil fuel combustion energy [GJ/Mt]
     pub fossil_fuel_combustion_energy: f32,
+    /// The ratio of extracted fossil fuel leaked as methane
+    pub fossil_fuel_methane_leak_ratio: f32,
     /// The ratio of available fossil fuel per cycle.
     pub available_fossil_fuel_ratio: f32,
     /// Basic nuclear supply ratio
@@ -891,6 +1399,8 @@ pub struct EventParams {
     pub fire_burn_ratio: f32,
     /// Biomass at fire extinction [kg/m2]
     pub biomass_at_fire_extinction_range: (f32, f32),
+    /// Radius of tiles that aerosol emitted by events spreads over
+    pub aerosol_emission_radius: u32,
     /// Aerosol supply by fire
     pub fire_aerosol: f32,
     /// Black dust albedo
@@ -947,6 +1457,8 @@ pub struct EventParams {
     pub nuclear_explosion_biomass_burn_ratio: f32,
     /// Aerosol supply by nuclear explosion
     pub nuclear_explosion_aerosol: f32,
+    /// Ozone layer damage by nuclear explosion at one cycle
+    pub nuclear_explosion_ozone_damage: f32,
     /// Probability to cause nuclear war per cycle
     pub nuclear_war_prob: [f64; CivilizationAge::LEN],
     /// Nuclear war duration cycles
@@ -967,6 +1479,8 @@ pub struct EventParams {
     pub settlement_exodus_cycles: u32,
     /// Volcanic eruption probability
     pub volcanic_eruption_prob: f64,
+    /// Probability that a volcanic eruption occurs at converging plate boundaries
+    pub volcanic_eruption_plate_boundary_prob: f64,
     /// Volcanic eruption cycles
     pub volcanic_eruption_cycles: (u32, u32),
     /// Volcanic eruption power
@@ -1039,6 +1553,9 @@ pub struct NewPlanetPercentageParam {
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct CustomPlanetParams {
     pub solar_constant: NewPlanetRangedParam,
+    /// Axial tilt [deg]
+    pub axial_tilt: NewPlanetRangedParam,
+    pub eccentricity: NewPlanetRangedParam,
     pub difference_in_elevation: NewPlanetRangedParam,
     pub water_volume: NewPlanetPercentageParam,
     pub nitrogen: NewPlanetPercentageParam,
@@ -1060,6 +1577,13 @@ pub struct StartPlanet {
     pub solar_constant: (f32, f32),
     #[serde(default, with = "serde_with::rust::unwrap_or_skip")]
     pub geothermal_power: Option<(f32, f32)>,
+    /// Axial tilt [deg]
+    #[serde(default, with = "serde_with::rust::unwrap_or_skip")]
+    pub axial_tilt: Option<(f32, f32)>,
+    #[serde(default, with = "serde_with::rust::unwrap_or_skip")]
+    pub eccentricity: Option<(f32, f32)>,
+    #[serde(default, with = "serde_with::rust::unwrap_or_skip")]
+    pub magnetosphere: Option<(f32, f32)>,
     pub elevation: (f32, f32),
     pub water_volume: (f32, f32),
     #[serde(default, with = "serde_with::rust::unwrap_or_skip")]
@@ -1070,6 +1594,8 @@ pub struct StartPlanet {
     pub carbon_dioxide: Option<(f32, f32)>,
     #[serde(default, with = "serde_with::rust::unwrap_or_skip")]
     pub argon: Option<(f32, f32)>,
+    #[serde(default, with = "serde_with::rust::unwrap_or_skip")]
+    pub methane: Option<(f32, f32)>,
     pub initial_conditions: Vec<InitialCondition>,
     #[serde(default)]
     pub height_table: Vec<(f32, f32)>,
@@ -1101,6 +1627,7 @@ pub struct MonitoringParams {
     pub warn_low_temp_threshold: f32,
     pub warn_low_oxygen_threshold: f32,
     pub warn_low_carbon_dioxide_threshold: f32,
+    pub warn_runaway_greenhouse_threshold: f32,
 }
 
 #[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
@@ -0,0 +1,68 @@
+use super::misc::linear_interpolation;
+use super::*;
+
+/// Molecular weight of hydrogen escaping from water vapor [g/mol]
+const MOLECULAR_WEIGHT_H: f32 = 1.0;
+
+/// Light gases and water escape to space from the upper atmosphere
+pub fn sim_atmospheric_escape(planet: &mut Planet, params: &Params) {
+    let relative_radius = planet.basics.radius / params.default_start_params.basics.radius;
+    let relative_solar_power =
+        planet.state.solar_power / params.default_start_params.basics.solar_constant;
+    let exobase_temp = planet.exobase_temp(params);
+
+    // Stellar wind strips the upper atmosphere unless the magnetosphere deflects it
+    let non_thermal = params.sim.non_thermal_escape_rate
+        * relative_solar_power
+        * (1.0 - planet.basics.magnetosphere).clamp(0.0, 1.0)
+        / relative_radius.powi(2);
+
+    let mut sum_escaped = 0.0;
+    for gas_kind in GasKind::iter() {
+        let ratio = jeans_escape_ratio(
+            super::atmo::molecular_weight(gas_kind),
+            relative_radius,
+            exobase_temp,
+            params,
+        ) + non_thermal;
+        let escaped = planet.atmo.mass(gas_kind) * ratio.min(1.0);
+        planet.atmo.add(gas_kind, -escaped);
+        sum_escaped += escaped;
+    }
+    planet.atmo.escape_rate = sum_escaped;
+
+    // Water vapor reaches the upper atmosphere only on hot planets, and its hydrogen escapes
+    let water_ratio =
+        (jeans_escape_ratio(MOLECULAR_WEIGHT_H, relative_radius, exobase_temp, params)
+            + non_thermal)
+            * linear_interpolation(
+                &params.sim.water_escape_temp_table,
+                planet.stat.average_air_temp,
+            );
+    let escaped_water = planet.water.water_volume * water_ratio.min(1.0);
+    planet.water.water_volume -= escaped_water;
+    planet.water.escape_rate = escaped_water;
+}
+
+/// The ratio of gas escaping thermally per cycle, derived from Jeans parameter
+fn jeans_escape_ratio(
+    molecular_weight: f32,
+    relative_radius: f32,
+    exobase_temp: f32,
+    params: &Params,
+) -> f32 {
+    let lambda = params.sim.jeans_parameter_hydrogen
+        * molecular_weight
+        * relative_radius.powi(2)
+        * (params.sim.exobase_temp / exobase_temp);
+    params.sim.jeans_escape_coef * (1.0 + lambda) * (-lambda).exp()
+}
+
+impl Planet {
+    /// Temperature of the exobase heated by stellar radiation [K]
+    pub fn exobase_temp(&self, params: &Params) -> f32 {
+        (params.sim.exobase_temp * self.state.solar_power
+            / params.default_start_params.basics.solar_constant)
+            .max(1.0)
+    }
+}
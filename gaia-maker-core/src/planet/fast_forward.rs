@@ -0,0 +1,76 @@
+use strum::{AsRefStr, EnumIter};
+
+use super::*;
+
+/// Conditions to stop fast-forwarding
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct FastForward {
+    /// The maximum number of cycles to advance
+    pub cycles: u64,
+    /// Stop when this requirement is satisfied
+    pub requirement: Option<Requirement>,
+    /// Stop when one of these kinds of reports is appended
+    pub reports: Vec<ReportKind>,
+}
+
+/// Kinds of reports that stop fast-forwarding
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize, EnumIter, AsRefStr)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "kebab-case")]
+pub enum ReportKind {
+    War,
+    Plague,
+    Exodus,
+    NewCiv,
+}
+
+/// The reason fast-forwarding stopped
+#[derive(Clone, Debug, AsRefStr)]
+#[strum(serialize_all = "kebab-case")]
+pub enum FastForwardStop {
+    Cycles,
+    Requirement,
+    Report(Report),
+    Cancelled,
+}
+
+impl ReportKind {
+    pub fn matches(&self, content: &ReportContent) -> bool {
+        match self {
+            ReportKind::War => matches!(
+                content,
+                ReportContent::EventInterSpeciesWar { .. } | ReportContent::EventNuclearWar {}
+            ),
+            ReportKind::Plague => matches!(content, ReportContent::EventPlague { .. }),
+            ReportKind::Exodus => matches!(content, ReportContent::EventExodus { .. }),
+            ReportKind::NewCiv => matches!(
+                content,
+                ReportContent::EventAchiveCivilization { .. }
+                    | ReportContent::EventCivilized { .. }
+            ),
+        }
+    }
+}
+
+impl FastForward {
+    /// Check the conditions after advancing from `start_cycles`
+    pub fn check(&self, planet: &Planet, start_cycles: u64) -> Option<FastForwardStop> {
+        if let Some(report) = planet
+            .reports
+            .iter()
+            .filter(|report| report.cycles == planet.cycles)
+            .find(|report| self.reports.iter().any(|kind| kind.matches(&report.content)))
+        {
+            return Some(FastForwardStop::Report(report.clone()));
+        }
+        if let Some(requirement) = &self.requirement
+            && requirement.check(planet)
+        {
+            return Some(FastForwardStop::Requirement);
+        }
+        if planet.cycles >= start_cycles + self.cycles {
+            return Some(FastForwardStop::Cycles);
+        }
+        None
+    }
+}
@@ -1,3 +1,5 @@
+use rand::seq::IndexedRandom;
+
 use super::*;
 
 pub fn advance_geological_event(planet: &mut Planet, sim: &mut Sim, params: &Params) {
@@ -7,11 +9,7 @@ pub fn advance_geological_event(planet: &mut Planet, sim: &mut Sim, params: &Par
         * params.event.volcanic_eruption_prob)
         .clamp(0.0, 1.0);
     if sim.rng.random_bool(prob) {
-        let (w, h) = planet.map.size();
-        let p = Coords::new(
-            sim.rng.random_range(0..w) as i32,
-            sim.rng.random_range(0..h) as i32,
-        );
+        let p = choose_volcano_tile(planet, sim, params);
         let event = volcanic_eruption_tile_event(planet, sim, params, false);
         planet.map[p].tile_events.insert(event);
     }
@@ -38,6 +36,29 @@ pub fn advance_geological_event(planet: &mut Planet, sim: &mut Sim, params: &Par
     }
 }
 
+/// Volcanoes are likely to appear at converging plate boundaries
+fn choose_volcano_tile(planet: &Planet, sim: &mut Sim, params: &Params) -> Coords {
+    if sim
+        .rng
+        .random_bool(params.event.volcanic_eruption_plate_boundary_prob)
+    {
+        let boundaries: Vec<Coords> = planet
+            .map
+            .iter_idx()
+            .filter(|p| sim.plate_convergence[*p] > 0.0)
+            .collect();
+        if let Some(p) = boundaries.choose(&mut sim.rng) {
+            return *p;
+        }
+    }
+
+    let (w, h) = planet.map.size();
+    Coords::new(
+        sim.rng.random_range(0..w) as i32,
+        sim.rng.random_range(0..h) as i32,
+    )
+}
+
 fn process_each_volcanic_eruption_event(
     planet: &mut Planet,
     sim: &mut Sim,
@@ -57,8 +78,8 @@ fn process_each_volcanic_eruption_event(
             tile.structure = None;
         }
         tile.animal = [None; AnimalSize::LEN];
-        if tile.biome.is_land() && tile.biome != Biome::Desert {
-            tile.biome = Biome::Rock;
+        if tile.biome.is_land(params) && tile.biome != Biome::DESERT {
+            tile.biome = Biome::ROCK;
         }
 
         let biomass = tile.biomass;
@@ -84,7 +105,13 @@ fn process_each_volcanic_eruption_event(
         tile.height += uplift;
     }
 
-    planet.atmo.aerosol += params.event.volcanic_eruption_aerosol * power;
+    super::cloud::emit_aerosol(
+        planet,
+        sim,
+        p_center,
+        params.event.volcanic_eruption_aerosol * power,
+        params,
+    );
     planet.atmo.add(
         GasKind::CarbonDioxide,
         params.event.volcanic_eruption_carbon_dioxide * power * power,
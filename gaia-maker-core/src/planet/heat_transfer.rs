@@ -1,4 +1,5 @@
 use super::misc::linear_interpolation;
+use super::wind::advection;
 use super::*;
 use geom::Direction;
 use rayon::prelude::*;
@@ -14,6 +15,7 @@ pub fn advance(planet: &mut Planet, sim: &mut Sim, params: &Params) {
     let air_heat_cap_per_tile = atmo_mass_per_tile * params.sim.air_heat_cap * 1.0E+9;
 
     calc_insolation(planet, sim, params);
+    super::ocean_current::update_ocean_currents(planet, sim, params);
 
     // Calculate heat capacity of tiles
     let par_iter = sim
@@ -23,9 +25,10 @@ pub fn advance(planet: &mut Planet, sim: &mut Sim, params: &Params) {
         .enumerate();
     par_iter.for_each(|(i, (atmo_heat_cap, sea_heat_cap))| {
         let p = Coords::from_index_size(i, size);
-        let surface_heat_cap = match planet.map[p].biome {
-            Biome::Ocean => params.sim.sea_heat_cap * params.sim.sea_surface_depth,
-            _ => params.sim.land_surface_heat_cap,
+        let surface_heat_cap = if planet.map[p].biome.is_open_sea(params) {
+            params.sim.sea_heat_cap * params.sim.sea_surface_depth
+        } else {
+            params.sim.land_surface_heat_cap
         };
         *atmo_heat_cap = air_heat_cap_per_tile + surface_heat_cap * sim.tile_area;
 
@@ -33,20 +36,28 @@ pub fn advance(planet: &mut Planet, sim: &mut Sim, params: &Params) {
         *sea_heat_cap = params.sim.sea_heat_cap * deep_layer_thickness * sim.tile_area;
     });
 
-    // Calculate albedo of tiles
-    let cloud_albedo = planet.cloud_albedo(params);
-    let par_iter = sim.albedo.par_iter_mut().zip(planet.map.par_iter());
-    par_iter.for_each(|(albedo, tile)| {
+    // Calculate albedo and greenhouse effect of tiles
+    let greenhouse_effect = greenhouse_effect(planet, params);
+    let par_iter = sim
+        .albedo
+        .par_iter_mut()
+        .zip(&mut sim.greenhouse_effect)
+        .enumerate();
+    par_iter.for_each(|(i, (albedo, tile_greenhouse_effect))| {
+        let p = Coords::from_index_size(i, size);
+        let tile = &planet.map[p];
         if tile.tile_events.contains(TileEventKind::BlackDust) {
             *albedo = params.event.black_dust_albedo;
         } else {
             let tile_albedo = params.biomes[&tile.biome].albedo;
+            let cloud_albedo = planet.tile_cloud_albedo(p, params);
             *albedo = (tile_albedo * tile_albedo + cloud_albedo * cloud_albedo).sqrt();
         }
+        *tile_greenhouse_effect = (greenhouse_effect
+            + linear_interpolation(&params.sim.cloud_green_house_effect_table, tile.cloud))
+        .clamp(0.0, 1.0);
     });
 
-    let greenhouse_effect = greenhouse_effect(planet, params);
-
     let secs_per_loop = params.sim.secs_per_cycle / params.sim.n_loop_atmo_heat_calc as f32;
 
     // Calculate new atmosphere temperature of tiles
@@ -58,7 +69,7 @@ pub fn advance(planet: &mut Planet, sim: &mut Sim, params: &Params) {
 
             let insolation = sim.insolation[p] * (1.0 - sim.albedo[p]);
 
-            let greenhouse_effect = greenhouse_effect
+            let greenhouse_effect = sim.greenhouse_effect[p]
                 * (1.0
                     - params.sim.green_house_effect_height_decrease
                         * planet.height_above_sea_level(p).max(0.0)
@@ -83,18 +94,26 @@ pub fn advance(planet: &mut Planet, sim: &mut Sim, params: &Params) {
                     }
                 })
                 .sum();
+            let wind_flow = advection(&sim.wind, &sim.atemp, p, &coords_converter, |_, _| {
+                params.sim.wind_heat_advection_factor * air_heat_cap_per_tile
+            });
 
-            let structure_heat = if let Some(BuildingEffect::Heater { heat }) =
-                planet.working_building_effect(p, params)
-            {
-                *heat
-            } else {
-                0.0
-            };
+            let structure_heat: f32 = planet
+                .working_building_effects(p, params)
+                .iter()
+                .map(|effect| {
+                    if let BuildingEffect::Heater { heat } = effect {
+                        *heat
+                    } else {
+                        0.0
+                    }
+                })
+                .sum();
 
             let heat_amount = old_heat_amount
                 + (inflow - outflow) * secs_per_loop
                 + adjacent_tile_flow
+                + wind_flow
                 + structure_heat;
             *atemp_new = heat_amount / sim.atmo_heat_cap[p];
         });
@@ -128,7 +147,17 @@ pub fn advance(planet: &mut Planet, sim: &mut Sim, params: &Params) {
                 }
             })
             .sum();
-        let heat_amount = old_heat_amount + adjacent_tile_flow;
+        let current_flow = advection(
+            &sim.ocean_current,
+            &sim.stemp,
+            p,
+            &coords_converter,
+            |from, to| {
+                params.sim.ocean_current_heat_advection_factor
+                    * sim.sea_heat_cap[from].min(sim.sea_heat_cap[to])
+            },
+        );
+        let heat_amount = old_heat_amount + adjacent_tile_flow + current_flow;
         tile.sea_temp = heat_amount / sim.sea_heat_cap[p];
     });
 
@@ -167,6 +196,10 @@ pub fn advance(planet: &mut Planet, sim: &mut Sim, params: &Params) {
     }
 
     planet.stat.average_air_temp = sum_temp as f32 / planet.n_tile() as f32;
+    let year = planet.cycles / params.sim.cycles_per_year.max(1);
+    planet
+        .stat
+        .record_air_temp_range(year, planet.stat.average_air_temp);
     if n_sea_tile > 0 {
         planet.stat.average_sea_temp = sum_sea_temp as f32 / n_sea_tile as f32;
     } else {
@@ -180,39 +213,61 @@ fn deep_sea_layer_thickness(p: Coords, planet: &Planet, params: &Params) -> f32
         .max(0.0)
 }
 
+/// Greenhouse effect by gases, excluding clouds
 fn greenhouse_effect(planet: &Planet, params: &Params) -> f32 {
-    (linear_interpolation(
+    linear_interpolation(
         &params.sim.co2_green_house_effect_table,
         planet.atmo.partial_pressure(GasKind::CarbonDioxide),
     ) + linear_interpolation(
-        &params.sim.cloud_green_house_effect_table,
-        planet.atmo.cloud_amount,
-    ))
-    .clamp(0.0, 1.0)
+        &params.sim.methane_green_house_effect_table,
+        planet.atmo.partial_pressure(GasKind::Methane),
+    ) + linear_interpolation(
+        &params.sim.vapor_green_house_effect_table,
+        planet.atmo.partial_pressure(GasKind::WaterVapor),
+    )
 }
 
 fn calc_insolation(planet: &Planet, sim: &mut Sim, params: &Params) {
     let solar_power = planet.state.solar_power;
-    if (sim.solar_constant_before - solar_power).abs() < 1.0e-3 {
+    // Insolation changes every cycle if the planet has seasons
+    if !planet.has_seasons() && (sim.solar_constant_before - solar_power).abs() < 1.0e-3 {
         return;
     }
     sim.solar_constant_before = solar_power;
 
+    let phase = planet.orbital_phase(params);
+    let declination = planet.basics.axial_tilt.to_radians() * phase.sin();
+    let e = planet.basics.eccentricity;
+    let distance_factor = ((1.0 + e * phase.cos()) / (1.0 - e * e)).powi(2);
+
     for p in planet.map.iter_idx() {
         let latitude = planet.calc_longitude_latitude(p).1;
+        // Add the seasonal deviation from the insolation without axial tilt
+        let seasonal =
+            daily_insolation_ratio(latitude, declination) - daily_insolation_ratio(latitude, 0.0);
         sim.insolation[p] = solar_power
-            * linear_interpolation(&params.sim.latitude_insolation_table, latitude.abs());
+            * distance_factor
+            * (linear_interpolation(&params.sim.latitude_insolation_table, latitude.abs())
+                + seasonal)
+                .max(0.0);
     }
 }
 
+/// Daily averaged insolation ratio to the solar constant at the latitude and solar declination
+fn daily_insolation_ratio(latitude: f32, declination: f32) -> f32 {
+    let hour_angle = (-latitude.tan() * declination.tan()).clamp(-1.0, 1.0).acos();
+    (hour_angle * latitude.sin() * declination.sin()
+        + latitude.cos() * declination.cos() * hour_angle.sin())
+        / std::f32::consts::PI
+}
+
 // Calculate initial temperature at the first simulation
-pub fn init_temp(planet: &mut Planet, sim: &mut Sim, params: &Params) {
-    let greenhouse_effect = greenhouse_effect(planet, params);
+pub fn init_temp(planet: &mut Planet, sim: &mut Sim, _params: &Params) {
     let map_iter_idx = planet.map.iter_idx();
 
     for p in map_iter_idx {
         let t4 = (1.0 - sim.albedo[p]) * sim.insolation[p]
-            / (STEFAN_BOLTZMANN_CONSTANT * (1.0 - greenhouse_effect));
+            / (STEFAN_BOLTZMANN_CONSTANT * (1.0 - sim.greenhouse_effect[p]));
         let t = t4.sqrt().sqrt();
         planet.map[p].temp = t;
         if sim.sea_heat_cap[p] > 0.0 {
@@ -222,7 +277,13 @@ pub fn init_temp(planet: &mut Planet, sim: &mut Sim, params: &Params) {
 }
 
 impl Planet {
-    pub fn cloud_albedo(&self, params: &Params) -> f32 {
-        linear_interpolation(&params.sim.cloud_albedo_table, self.atmo.cloud_amount)
+    pub fn has_seasons(&self) -> bool {
+        self.basics.axial_tilt != 0.0 || self.basics.eccentricity != 0.0
+    }
+
+    /// Orbital position in a year [rad]. Zero at the perihelion and the northern vernal equinox
+    pub fn orbital_phase(&self, params: &Params) -> f32 {
+        let cycles_per_year = params.sim.cycles_per_year.max(1);
+        2.0 * std::f32::consts::PI * (self.cycles % cycles_per_year) as f32 / cycles_per_year as f32
     }
 }
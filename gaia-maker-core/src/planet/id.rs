@@ -0,0 +1,93 @@
+use std::fmt;
+use std::hash::{Hash, Hasher};
+
+pub const ID_MAX_LEN: usize = 23;
+
+/// Short string id of assets registered in lists.
+/// It is `Copy` and can be created in const context.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct IdStr {
+    len: u8,
+    bytes: [u8; ID_MAX_LEN],
+}
+
+impl IdStr {
+    pub const fn new(id: &str) -> Self {
+        let src = id.as_bytes();
+        assert!(!src.is_empty() && src.len() <= ID_MAX_LEN);
+        let mut bytes = [0; ID_MAX_LEN];
+        let mut i = 0;
+        while i < src.len() {
+            bytes[i] = src[i];
+            i += 1;
+        }
+        IdStr {
+            len: src.len() as u8,
+            bytes,
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        std::str::from_utf8(&self.bytes[..self.len as usize]).unwrap()
+    }
+}
+
+impl Hash for IdStr {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_str().hash(state);
+    }
+}
+
+impl PartialOrd for IdStr {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for IdStr {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.as_str().cmp(other.as_str())
+    }
+}
+
+impl AsRef<str> for IdStr {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl fmt::Display for IdStr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl fmt::Debug for IdStr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}", self.as_str())
+    }
+}
+
+impl std::str::FromStr for IdStr {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.is_empty() || s.len() > ID_MAX_LEN {
+            return Err(format!("invalid id \"{}\"", s));
+        }
+        Ok(IdStr::new(s))
+    }
+}
+
+impl serde::Serialize for IdStr {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for IdStr {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = <std::borrow::Cow<'de, str>>::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
@@ -6,17 +6,17 @@ pub fn apply_initial_condition(
     planet: &mut Planet,
     sim: &mut Sim,
     initial_condition: InitialCondition,
-    _params: &Params,
+    params: &Params,
 ) {
     match initial_condition {
         InitialCondition::Snowball { thickness } => {
             for p in planet.map.iter_idx() {
                 let t = 250.0;
                 let tile = &mut planet.map[p];
-                if tile.biome.is_land() {
-                    tile.biome = Biome::IceSheet;
+                if tile.biome.is_land(params) {
+                    tile.biome = Biome::ICE_SHEET;
                 } else {
-                    tile.biome = Biome::SeaIce;
+                    tile.biome = Biome::SEA_ICE;
                     tile.sea_temp = t;
                 }
                 tile.ice = sim.rng.sample(SymmetricalLinearDist::from(thickness));
@@ -0,0 +1,354 @@
+use std::collections::BTreeMap;
+
+use super::debug::PlanetDebug;
+use super::*;
+
+/// Maximum number of journal entries. The journal is cloned with the planet for undo and
+/// snapshots, so longer journals are discarded
+const MAX_JOURNAL_ENTRIES: usize = 10000;
+
+/// Commands given by the player
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PlayerAction {
+    Build {
+        p: Coords,
+        kind: StructureKind,
+    },
+    Demolition {
+        p: Coords,
+    },
+    SpawnAnimal {
+        p: Coords,
+        id: AnimalId,
+    },
+    /// Civilize an animal, or place a settlement if the civilization already exists
+    Civilize {
+        p: Coords,
+        id: AnimalId,
+    },
+    CauseTileEvent {
+        p: Coords,
+        kind: TileEventKind,
+    },
+    BuildSpaceBuilding {
+        kind: SpaceBuildingKind,
+    },
+    DemolishSpaceBuilding {
+        kind: SpaceBuildingKind,
+        n: u32,
+    },
+    Control(ControlValues),
+}
+
+/// Values the player can change by control sliders
+#[derive(Clone, PartialEq, Default, Debug, Serialize, Deserialize)]
+pub struct ControlValues {
+    pub space_buildings: BTreeMap<SpaceBuildingKind, BuildingControlValue>,
+    pub forestation_speed: i16,
+    pub animal_evolution: i16,
+    pub civ_prob: i16,
+    pub civs: BTreeMap<AnimalId, CivControl>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct JournalEntry {
+    pub cycles: u64,
+    /// Seed for the random number generator used by the action
+    pub seed: u64,
+    pub action: PlayerAction,
+    /// Number of times the action is repeated after the first one. The seed of the n-th
+    /// repetition is `seed + n`
+    #[serde(default)]
+    pub repeat: u32,
+}
+
+/// Record of player actions to reproduce a game from the start
+#[derive(Clone, Default, Debug, Serialize, Deserialize)]
+pub struct Journal {
+    /// Start parameters with the seed. `None` for planets created before journals were recorded,
+    /// or planets changed in ways that cannot be replayed
+    #[serde(default, with = "super::serde_with_types::unwrap_or_unit")]
+    pub start_params: Option<StartParams>,
+    pub entries: Vec<JournalEntry>,
+}
+
+impl Journal {
+    pub fn new(start_params: &StartParams, seed: u64) -> Self {
+        Self {
+            start_params: Some(StartParams {
+                seed: Some(seed),
+                ..start_params.clone()
+            }),
+            entries: Vec::new(),
+        }
+    }
+
+    pub fn replayable(&self) -> bool {
+        self.start_params.is_some()
+    }
+
+    /// Discard the journal because the planet can no longer be reproduced from it
+    pub fn invalidate(&mut self) {
+        if self.replayable() {
+            log::info!("journal invalidated");
+        }
+        self.start_params = None;
+        self.entries = Vec::new();
+    }
+
+    /// Seed for the action if it repeats the last entry in the same cycle
+    fn repeat_seed(&self, cycles: u64, action: &PlayerAction) -> Option<u64> {
+        self.entries
+            .last()
+            .filter(|last| {
+                last.cycles == cycles
+                    && last.action == *action
+                    && !matches!(action, PlayerAction::Control(_))
+            })
+            .map(|last| last.seed.wrapping_add(last.repeat as u64 + 1))
+    }
+
+    fn push(&mut self, cycles: u64, seed: u64, action: PlayerAction) {
+        if !self.replayable() {
+            return;
+        }
+        // Merge control changes in the same cycle, because a slider changes values continuously
+        if let PlayerAction::Control(_) = action
+            && let Some(last) = self.entries.last_mut()
+            && last.cycles == cycles
+            && matches!(last.action, PlayerAction::Control(_))
+        {
+            last.action = action;
+            return;
+        }
+        // Repeated actions such as building many space buildings are stored in one entry
+        if self.repeat_seed(cycles, &action) == Some(seed) {
+            self.entries.last_mut().unwrap().repeat += 1;
+            return;
+        }
+        if self.entries.len() >= MAX_JOURNAL_ENTRIES {
+            log::warn!("journal exceeds {} entries", MAX_JOURNAL_ENTRIES);
+            self.invalidate();
+            return;
+        }
+        self.entries.push(JournalEntry {
+            cycles,
+            seed,
+            action,
+            repeat: 0,
+        });
+    }
+}
+
+impl Planet {
+    pub fn control_values(&self) -> ControlValues {
+        ControlValues {
+            space_buildings: self
+                .space_buildings
+                .iter()
+                .map(|(kind, building)| (*kind, building.control))
+                .collect(),
+            forestation_speed: self.state.forestation_speed,
+            animal_evolution: self.state.animal_evolution,
+            civ_prob: self.state.civ_prob,
+            civs: self
+                .civs
+                .iter()
+                .map(|(id, civ)| (*id, civ.civ_control.clone()))
+                .collect(),
+        }
+    }
+
+    /// Check whether the action can be performed now
+    pub fn action_applicable(&self, action: &PlayerAction, params: &Params) -> bool {
+        match action {
+            PlayerAction::Build { p, kind } => {
+                kind.buildable_by_player()
+                    && params.structures.contains_key(kind)
+                    && self.buildable(params.structures[kind].as_ref()).is_ok()
+                    && self.structure_unlocked(*kind, params)
+                    && self.placeable(*p, *kind, params)
+            }
+            PlayerAction::Demolition { p } => self.map.in_range(*p) && self.demolishable(*p),
+            PlayerAction::SpawnAnimal { p, id } => {
+                self.map.in_range(*p)
+                    && params.animals.contains_key(id)
+                    && self.animal_spawnable(*p, *id, params)
+            }
+            PlayerAction::Civilize { p, id } => {
+                self.map.in_range(*p)
+                    && self.res.gene_point >= params.event.civilize_cost
+                    && self
+                        .get_civilizable_animal(*p, params)
+                        .is_some_and(|animal_id| animal_id == *id)
+                    && (!self.civs.contains_key(id) || self.map[*p].structure.is_none())
+            }
+            PlayerAction::CauseTileEvent { p, kind } => {
+                self.map.in_range(*p) && self.tile_event_causable(*kind, params)
+            }
+            PlayerAction::BuildSpaceBuilding { kind } => {
+                let attrs = params.building_attrs(*kind);
+                attrs
+                    .build_max
+                    .is_none_or(|max| self.space_building(*kind).n < max)
+                    && self.buildable(attrs).is_ok()
+            }
+            PlayerAction::DemolishSpaceBuilding { kind, .. } => self.space_building(*kind).n > 0,
+            PlayerAction::Control(_) => true,
+        }
+    }
+
+    /// Perform a player action and record it to the journal. Returns false if not applicable
+    pub fn apply_action(&mut self, action: &PlayerAction, sim: &mut Sim, params: &Params) -> bool {
+        if !self.action_applicable(action, params) {
+            return false;
+        }
+        let seed = if matches!(action, PlayerAction::Control(_)) {
+            0
+        } else if let Some(seed) = self.journal.repeat_seed(self.cycles, action) {
+            seed
+        } else {
+            sim.rng.random()
+        };
+        self.apply_action_with_seed(action, seed, sim, params)
+    }
+
+    fn apply_action_with_seed(
+        &mut self,
+        action: &PlayerAction,
+        seed: u64,
+        sim: &mut Sim,
+        params: &Params,
+    ) -> bool {
+        if !self.action_applicable(action, params) {
+            return false;
+        }
+        if !matches!(action, PlayerAction::Control(_)) {
+            sim.rng = misc::get_seeded_rng(seed, misc::RNG_STREAM_ACTION);
+        }
+
+        match action.clone() {
+            PlayerAction::Build { p, kind } => {
+                self.place(p, Structure::new_building(kind), sim, params);
+            }
+            PlayerAction::Demolition { p } => {
+                self.demolition(p, sim, params);
+            }
+            PlayerAction::SpawnAnimal { p, id } => {
+                self.spawn_animal(p, id, params);
+            }
+            PlayerAction::Civilize { p, id } => {
+                if self.civs.contains_key(&id) {
+                    let age = CivilizationAge::Stone;
+                    self.place_settlement(
+                        p,
+                        Settlement {
+                            id,
+                            age,
+                            pop: params.sim.settlement_init_pop[age as usize],
+                            ..Default::default()
+                        },
+                    );
+                    self.res.consume(Cost::GenePoint(params.event.civilize_cost));
+                } else {
+                    self.civilize_animal(p, id, params);
+                }
+            }
+            PlayerAction::CauseTileEvent { p, kind } => {
+                self.cause_tile_event(p, kind, sim, params);
+            }
+            PlayerAction::BuildSpaceBuilding { kind } => {
+                self.build_space_building(kind, sim, params);
+            }
+            PlayerAction::DemolishSpaceBuilding { kind, n } => {
+                self.demolish_space_building(kind, n, sim, params);
+            }
+            PlayerAction::Control(values) => {
+                self.set_control_values(&values);
+            }
+        }
+
+        let replayable = self.journal.replayable();
+        self.journal.push(self.cycles, seed, action.clone());
+        if replayable && !self.journal.replayable() {
+            self.reports
+                .append(self.cycles, ReportContent::EventJournalDiscarded {});
+        }
+        true
+    }
+
+    fn set_control_values(&mut self, values: &ControlValues) {
+        for (kind, control) in &values.space_buildings {
+            let building = self.space_building_mut(*kind);
+            if building.n > 0 {
+                building.control = *control;
+            }
+        }
+        self.state.forestation_speed = values.forestation_speed;
+        self.state.animal_evolution = values.animal_evolution;
+        self.state.civ_prob = values.civ_prob;
+        for (id, civ_control) in &values.civs {
+            if let Some(civ) = self.civs.get_mut(id) {
+                civ.civ_control = civ_control.clone();
+            }
+        }
+    }
+}
+
+/// Re-runs a game from the start parameters of a journal
+#[derive(Clone, Debug)]
+pub struct Replay {
+    entries: Vec<JournalEntry>,
+    next: usize,
+}
+
+impl Replay {
+    /// Create a replay and the planet at the start. Returns `None` if the journal is not replayable
+    pub fn new(journal: &Journal, params: &Params) -> Option<(Self, Planet)> {
+        let start_params = journal.start_params.as_ref()?;
+        let planet = Planet::new(start_params, params);
+        let replay = Self {
+            entries: journal
+                .entries
+                .iter()
+                .flat_map(|entry| {
+                    (0..=entry.repeat).map(|n| JournalEntry {
+                        seed: entry.seed.wrapping_add(n as u64),
+                        repeat: 0,
+                        ..entry.clone()
+                    })
+                })
+                .collect(),
+            next: 0,
+        };
+        Some((replay, planet))
+    }
+
+    /// Apply actions recorded at the current cycles. Call this before `Planet::advance`
+    pub fn apply_actions(&mut self, planet: &mut Planet, sim: &mut Sim, params: &Params) {
+        while let Some(entry) = self.entries.get(self.next)
+            && entry.cycles <= planet.cycles
+        {
+            if entry.cycles < planet.cycles
+                || !planet.apply_action_with_seed(&entry.action, entry.seed, sim, params)
+            {
+                log::warn!(
+                    "cannot replay action at cycles {}: {:?}",
+                    entry.cycles,
+                    entry.action
+                );
+            }
+            self.next += 1;
+        }
+    }
+
+    pub fn finished(&self) -> bool {
+        self.next >= self.entries.len()
+    }
+
+    /// Cycles of the last recorded action
+    pub fn last_cycles(&self) -> u64 {
+        self.entries.last().map(|entry| entry.cycles).unwrap_or(0)
+    }
+}
@@ -6,6 +6,7 @@ use noise::{
 
 #[derive(Clone, Debug)]
 pub struct GenConf {
+    pub seed: u32,
     pub w: u32,
     pub h: u32,
     pub max_height: f32,
@@ -18,7 +19,7 @@ pub fn generate(conf: GenConf) -> Array2d<f32> {
         return map;
     }
 
-    let noise_fn = ScalePoint::new(Perlin::new(rand::random())).set_scale(2.0);
+    let noise_fn = ScalePoint::new(Perlin::new(conf.seed)).set_scale(2.0);
     let map_builder = SphereMapBuilder::new(noise_fn)
         .set_size(conf.w as _, conf.h as _)
         .set_bounds(-80.0, 80.0, -180.0, 180.0)
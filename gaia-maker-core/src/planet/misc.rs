@@ -152,9 +152,20 @@ impl From<(f32, f32)> for SymmetricalLinearDist {
     }
 }
 
-pub fn get_rng() -> SmallRng {
-    let mut thread_rng = rand::rng();
-    rand::rngs::SmallRng::from_rng(&mut thread_rng)
+/// Stream id for sampling start parameters
+pub const RNG_STREAM_START_PARAMS: u64 = u64::MAX;
+/// Stream id for map generation
+pub const RNG_STREAM_MAP: u64 = u64::MAX - 1;
+/// Stream id for locating initial buried carbon
+pub const RNG_STREAM_BURIED_CARBON: u64 = u64::MAX - 2;
+/// Stream for player actions
+pub const RNG_STREAM_ACTION: u64 = u64::MAX - 3;
+/// Stream id for generating tectonic plates
+pub const RNG_STREAM_PLATES: u64 = u64::MAX - 4;
+
+/// Get a deterministic random number generator for the given planet seed and stream id
+pub fn get_seeded_rng(seed: u64, stream: u64) -> SmallRng {
+    SmallRng::seed_from_u64(seed ^ stream.wrapping_mul(0x9E37_79B9_7F4A_7C15))
 }
 
 impl rand::distr::Distribution<f32> for SymmetricalLinearDist {
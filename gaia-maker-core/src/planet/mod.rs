@@ -4,41 +4,60 @@ mod animal;
 mod atmo;
 mod biome;
 mod buildings;
+mod carbon_cycle;
 mod civ;
 mod civ_energy;
+mod cloud;
 mod decadence;
 mod defs;
+mod escape;
 mod event;
 mod exodus;
+mod fast_forward;
 mod geological_event;
 mod heat_transfer;
+mod id;
 mod initial_conditions;
+mod journal;
 mod map_generator;
 mod misc;
 mod monitoring;
 mod new;
+mod ocean_current;
 mod plague;
+mod profiler;
 mod report;
 mod requirement;
 mod resources;
+mod river;
 mod serde_with_types;
 mod sim;
+mod species;
 mod stat;
+mod terrain;
 mod tile_event;
 mod war;
 mod water;
+mod wind;
 
 pub mod debug;
 
 pub use self::achivement::{ACHIVEMENTS, Achivement, check_achivements};
+pub use self::animal::{CIVILIZEABLE, FoodWeb};
 pub use self::atmo::Atmosphere;
 use self::civ::Civs;
+pub use self::civ::set_default_civ_name_fn;
 pub use self::defs::*;
 pub use self::event::*;
+pub use self::fast_forward::{FastForward, FastForwardStop, ReportKind};
+pub use self::id::IdStr;
+pub use self::journal::{ControlValues, Journal, JournalEntry, PlayerAction, Replay};
+pub use self::profiler::{Profiler, StageTime};
 pub use self::report::*;
 pub use self::requirement::Requirement;
 pub use self::resources::*;
 pub use self::sim::Sim;
+pub use self::species::Species;
 pub use self::stat::{Record, Stat};
 pub use self::tile_event::TileEvents;
 pub use self::water::*;
@@ -46,6 +65,7 @@ pub use self::water::*;
 use fnv::FnvHashMap;
 use geom::{Array2d, Coords};
 use misc::SymmetricalLinearDist;
+use profiler::StageTimer;
 use rand::Rng;
 use serde::{Deserialize, Serialize};
 use std::f32::consts::PI;
@@ -71,12 +91,32 @@ pub struct Tile {
     pub buried_carbon: f32,
     pub ice: f32,
     pub tile_events: TileEvents,
+    /// Accumulated rainfall of upstream tiles [mm]
+    #[serde(default)]
+    pub river_flow: f32,
+    /// Water level of the lake [m]. `None` if the tile is not a lake
+    #[serde(default)]
+    pub lake_level: Option<f32>,
+    /// Aerosol amount in the air over the tile
+    #[serde(default = "tile_aerosol_default")]
+    pub aerosol: f32,
+    /// Cloud amount over the tile [%]
+    #[serde(default = "tile_cloud_default")]
+    pub cloud: f32,
+}
+
+fn tile_aerosol_default() -> f32 {
+    atmo::AEROSOL_EQUILIBRIUM_TARGET
+}
+
+fn tile_cloud_default() -> f32 {
+    50.0
 }
 
 impl Default for Tile {
     fn default() -> Self {
         Self {
-            biome: Biome::Rock,
+            biome: Biome::ROCK,
             structure: None,
             animal: [None, None, None],
             height: 0.0,
@@ -89,6 +129,10 @@ impl Default for Tile {
             buried_carbon: 0.0,
             ice: 0.0,
             tile_events: TileEvents::default(),
+            river_flow: 0.0,
+            lake_level: None,
+            aerosol: tile_aerosol_default(),
+            cloud: tile_cloud_default(),
         }
     }
 }
@@ -129,25 +173,52 @@ pub struct Planet {
     pub civs: Civs,
     pub stat: Stat,
     pub reports: Reports,
+    #[serde(default)]
+    pub journal: Journal,
+    /// Species generated on this planet
+    #[serde(default)]
+    pub species: FnvHashMap<AnimalId, Species>,
+    /// Random seed for this planet
+    #[serde(default)]
+    pub seed: u64,
 }
 
 impl Planet {
     pub fn advance(&mut self, sim: &mut Sim, params: &Params) {
+        let mut timer = StageTimer::start();
         self.update(sim, params);
         self.cycles += 1;
         self.res.apply_diff();
+        sim.rng = misc::get_seeded_rng(self.seed, self.cycles);
+        timer.lap(sim, "update");
 
         self::atmo::sim_atmosphere(self, sim, params);
+        timer.lap(sim, "atmosphere");
         self::civ_energy::update_civ_energy(self, sim, params);
+        timer.lap(sim, "civ_energy");
         self::tile_event::advance(self, sim, params);
+        timer.lap(sim, "tile_event");
         self::buildings::advance(self, sim, params);
+        timer.lap(sim, "buildings");
         self::heat_transfer::advance(self, sim, params);
+        timer.lap(sim, "heat_transfer");
         self::water::sim_water(self, sim, params);
+        timer.lap(sim, "water");
+        self::carbon_cycle::sim_carbon_cycle(self, sim, params);
+        timer.lap(sim, "carbon_cycle");
+        self::terrain::sim_terrain(self, sim, params);
+        timer.lap(sim, "terrain");
         self::biome::sim_biome(self, sim, params);
+        timer.lap(sim, "biome");
         self::animal::sim_animal(self, sim, params);
+        timer.lap(sim, "animal");
         self::civ::sim_civs(self, sim, params);
+        timer.lap(sim, "civ");
         self::event::advance(self, sim, params);
+        timer.lap(sim, "event");
         self::stat::record_stats(self, params);
+        timer.lap(sim, "stat");
+        timer.finish(sim);
     }
 
     /// Update after user action without advance the cycle
@@ -162,7 +233,7 @@ impl Planet {
         self.state.solar_power = self.basics.solar_constant * self.state.solar_power_multiplier;
 
         // Add gene point based on planet biomass
-        self.res.diff_gene_point =
+        self.res.diff_gene_point +=
             (self.stat.sum_biomass / params.sim.coef_gene_point_income).sqrt();
     }
 
@@ -188,8 +259,9 @@ impl Planet {
     }
 }
 
-pub fn start_planet_to_start_params(id: &str, params: &Params) -> StartParams {
-    let mut rng = misc::get_rng();
+pub fn start_planet_to_start_params(id: &str, params: &Params, seed: Option<u64>) -> StartParams {
+    let seed = seed.unwrap_or_else(rand::random);
+    let mut rng = misc::get_seeded_rng(seed, misc::RNG_STREAM_START_PARAMS);
 
     let start_planet = params
         .start_planets
@@ -215,6 +287,10 @@ pub fn start_planet_to_start_params(id: &str, params: &Params) -> StartParams {
         *atmo.get_mut(&GasKind::Argon).unwrap() =
             rng.sample(SymmetricalLinearDist::from(range)).into();
     }
+    if let Some(range) = start_planet.methane {
+        *atmo.get_mut(&GasKind::Methane).unwrap() =
+            rng.sample(SymmetricalLinearDist::from(range)).into();
+    }
 
     StartParams {
         basics: Basics {
@@ -232,6 +308,19 @@ pub fn start_planet_to_start_params(id: &str, params: &Params) -> StartParams {
                 .geothermal_power
                 .map(|geothermal_power| rng.sample(SymmetricalLinearDist::from(geothermal_power)))
                 .unwrap_or(params.default_start_params.basics.geothermal_power),
+            axial_tilt: start_planet
+                .axial_tilt
+                .map(|axial_tilt| floor(0.1, rng.sample(SymmetricalLinearDist::from(axial_tilt))))
+                .unwrap_or(params.default_start_params.basics.axial_tilt),
+            eccentricity: start_planet
+                .eccentricity
+                .map(|eccentricity| rng.sample(SymmetricalLinearDist::from(eccentricity)))
+                .unwrap_or(params.default_start_params.basics.eccentricity),
+            retrograde_rotation: params.default_start_params.basics.retrograde_rotation,
+            magnetosphere: start_planet
+                .magnetosphere
+                .map(|magnetosphere| rng.sample(SymmetricalLinearDist::from(magnetosphere)))
+                .unwrap_or(params.default_start_params.basics.magnetosphere),
         },
         difference_in_elevation: rng.sample(SymmetricalLinearDist::from(start_planet.elevation)),
         water_volume: rng.sample(SymmetricalLinearDist::from(start_planet.water_volume)),
@@ -242,6 +331,7 @@ pub fn start_planet_to_start_params(id: &str, params: &Params) -> StartParams {
         target_sea_area: start_planet.target_sea_area,
         height_map: start_planet.height_map.clone(),
         initial_buried_carbon: start_planet.initial_buried_carbon.clone(),
+        seed: Some(seed),
         ..params.default_start_params.clone()
     }
 }
@@ -249,3 +339,12 @@ pub fn start_planet_to_start_params(id: &str, params: &Params) -> StartParams {
 fn floor(a: f32, f: f32) -> f32 {
     (f / a).floor() * a
 }
+
+#[cfg(feature = "bevy")]
+impl bevy_ecs::prelude::Resource for Planet {}
+#[cfg(feature = "bevy")]
+impl bevy_ecs::prelude::Resource for Params {}
+#[cfg(feature = "bevy")]
+impl bevy_ecs::prelude::Resource for Sim {}
+#[cfg(feature = "bevy")]
+impl bevy_ecs::prelude::Resource for Replay {}
@@ -42,5 +42,17 @@ impl Planet {
             self.reports
                 .remove_persitent_warn(&ReportContent::WarnLowCarbonDioxide);
         }
+
+        // Runaway greenhouse warning
+        if self.is_ocean_boiling()
+            || self.atmo.partial_pressure(GasKind::WaterVapor)
+                > params.monitoring.warn_runaway_greenhouse_threshold
+        {
+            self.reports
+                .append_persitent_warn(self.cycles, ReportContent::WarnRunawayGreenhouse);
+        } else {
+            self.reports
+                .remove_persitent_warn(&ReportContent::WarnRunawayGreenhouse);
+        }
     }
 }
@@ -1,12 +1,16 @@
+use std::sync::Arc;
+
 use sim::CoordsConverter;
 
 use super::*;
 
 impl Planet {
     pub fn new(start_params: &StartParams, params: &Params) -> Planet {
+        let seed = start_params.seed.unwrap_or_else(rand::random);
         let mut map = Array2d::new(start_params.size.0, start_params.size.1, Tile::default());
 
         let gen_conf = map_generator::GenConf {
+            seed: misc::get_seeded_rng(seed, misc::RNG_STREAM_MAP).random(),
             w: start_params.size.0,
             h: start_params.size.1,
             max_height: start_params.difference_in_elevation,
@@ -26,13 +30,18 @@ impl Planet {
             map,
             atmo: Atmosphere::new(start_params, params),
             water: Water::new(start_params),
-            space_buildings: SpaceBuildingKind::iter()
-                .map(|kind| (kind, Building::default()))
+            space_buildings: params
+                .space_buildings
+                .keys()
+                .map(|&kind| (kind, Building::default()))
                 .collect(),
             events: Events::default(),
             civs: Civs::default(),
             stat: Stat::new(params),
             reports: Reports::default(),
+            journal: Journal::new(start_params, seed),
+            species: FnvHashMap::default(),
+            seed,
         };
 
         for (&kind, &n) in &start_params.space_buildings {
@@ -41,7 +50,7 @@ impl Planet {
         }
         // Locate initial buried carbon
         if let Some(initial_buried_carbon) = &start_params.initial_buried_carbon {
-            locate_initial_buried_carbon(&mut planet, initial_buried_carbon);
+            locate_initial_buried_carbon(&mut planet, initial_buried_carbon, seed);
         }
 
         // Adjust water volume
@@ -63,8 +72,11 @@ impl Planet {
                     if let Some(target_sea_level) = target_sea_level {
                         planet.water.sea_level - target_sea_level
                     } else {
-                        let n_sea_tile =
-                            planet.map.iter().filter(|tile| tile.biome.is_sea()).count();
+                        let n_sea_tile = planet
+                            .map
+                            .iter()
+                            .filter(|tile| tile.biome.is_sea(params))
+                            .count();
                         let size = planet.map.size();
                         let ratio = n_sea_tile as f32 / (size.0 * size.1) as f32;
                         ratio - start_params.target_sea_area.unwrap()
@@ -112,14 +124,45 @@ impl Planet {
         // Reset
         planet.cycles = 0;
         planet.stat.clear_history();
-        planet.res.material = 0.0;
+        planet.res.material = start_params.material_at_start.unwrap_or(0.0);
         self::stat::record_stats(&mut planet, params);
 
         planet
     }
+
+    /// Fill values that are missing in saves of older versions, and replace ids unknown to
+    /// the current assets
+    pub fn migrate(&mut self, params: &Params) {
+        // Biomes removed from assets are replaced by the basic biome of the tile
+        for p in self.map.iter_idx() {
+            if !params.biomes.contains_key(&self.map[p].biome) {
+                log::warn!("unknown biome \"{}\" at {:?}", self.map[p].biome, p);
+                self.map[p].biome = if self.is_ocean_tile(p) {
+                    Biome::OCEAN
+                } else {
+                    Biome::ROCK
+                };
+            }
+        }
+        for species in self.species.values_mut() {
+            if let AnimalHabitat::Biomes(biomes) = &mut Arc::make_mut(&mut species.attr).habitat {
+                biomes.retain(|biome| params.biomes.contains_key(biome));
+            }
+        }
+
+        // The ocean is assumed to be in equilibrium with the atmosphere
+        if self.water.dissolved_carbon == 0.0 {
+            self.water.dissolved_carbon =
+                super::carbon_cycle::ocean_carbon_equilibrium(self, params);
+        }
+    }
 }
 
-fn locate_initial_buried_carbon(planet: &mut Planet, initial_buried_carbon: &InitialBuriedCarbon) {
+fn locate_initial_buried_carbon(
+    planet: &mut Planet,
+    initial_buried_carbon: &InitialBuriedCarbon,
+    seed: u64,
+) {
     let coords_converter = CoordsConverter::new(planet);
     let size = planet.map.size();
     let InitialBuriedCarbon {
@@ -128,7 +171,7 @@ fn locate_initial_buried_carbon(planet: &mut Planet, initial_buried_carbon: &Ini
         radius,
         scattering,
     } = *initial_buried_carbon;
-    let mut rng = super::misc::get_rng();
+    let mut rng = super::misc::get_seeded_rng(seed, super::misc::RNG_STREAM_BURIED_CARBON);
     let n_spot = rng.random_range(n_spot.0..n_spot.1);
 
     for _ in 0..n_spot {
@@ -0,0 +1,74 @@
+use geom::Direction;
+
+use super::*;
+
+/// Recalculate surface ocean currents if coastlines are changed
+pub fn update_ocean_currents(planet: &Planet, sim: &mut Sim, params: &Params) {
+    let sea_tiles: Vec<bool> = planet.map.iter_idx().map(|p| planet.is_ocean_tile(p)).collect();
+    if sea_tiles == sim.sea_tiles_before {
+        return;
+    }
+    calc_ocean_currents(planet, sim, params);
+    sim.sea_tiles_before = sea_tiles;
+}
+
+/// Calculate surface ocean currents driven by prevailing wind and deflected by coastlines
+fn calc_ocean_currents(planet: &Planet, sim: &mut Sim, params: &Params) {
+    let size = planet.map.size();
+    let map_iter_idx = planet.map.iter_idx();
+    let coords_converter = sim.coords_converter();
+    let is_sea = |p: Coords| planet.is_ocean_tile(p);
+
+    let mut current = Array2d::new(size.0, size.1, (0.0, 0.0));
+    for p in map_iter_idx {
+        if !is_sea(p) {
+            continue;
+        }
+        let poleward = planet.calc_longitude_latitude(p).1.signum();
+        let (mut eastward, mut northward) = sim.wind[p];
+
+        // Western boundary currents flow poleward, and eastern boundary currents flow equatorward
+        let dx = if eastward > 0.0 { 1 } else { -1 };
+        if !coords_converter
+            .conv(p + Coords::new(dx, 0))
+            .is_some_and(is_sea)
+        {
+            northward -= eastward * poleward;
+            eastward = 0.0;
+        }
+        let dy = if northward > 0.0 { 1 } else { -1 };
+        if !coords_converter
+            .conv(p + Coords::new(0, dy))
+            .is_some_and(is_sea)
+        {
+            northward = 0.0;
+        }
+        current[p] = (eastward, northward);
+    }
+
+    // Smooth currents to connect flows along coastlines
+    for _ in 0..params.sim.n_loop_ocean_current_smoothing {
+        let prev = current.clone();
+        for p in map_iter_idx {
+            if !is_sea(p) {
+                continue;
+            }
+            let (mut sum_x, mut sum_y) = prev[p];
+            let mut n = 1.0;
+            for dir in Direction::FOUR_DIRS {
+                if let Some(adjacent_tile) = coords_converter
+                    .conv(p + dir.as_coords())
+                    .filter(|adjacent_tile| is_sea(*adjacent_tile))
+                {
+                    let (x, y) = prev[adjacent_tile];
+                    sum_x += x;
+                    sum_y += y;
+                    n += 1.0;
+                }
+            }
+            current[p] = (sum_x / n, sum_y / n);
+        }
+    }
+
+    sim.ocean_current = current;
+}
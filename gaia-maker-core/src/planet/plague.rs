@@ -2,7 +2,7 @@ use arrayvec::ArrayVec;
 use geom::Coords;
 use rand::{Rng, seq::IndexedRandom};
 
-use super::{Planet, Sim, defs::*};
+use super::{Planet, ReportContent, Sim, defs::*};
 
 pub fn cause_plague(planet: &mut Planet, _sim: &mut Sim, params: &Params, p: Coords) {
     let plague_event: &mut PlagueEvent = 'a: {
@@ -16,6 +16,9 @@ pub fn cause_plague(planet: &mut Planet, _sim: &mut Sim, params: &Params, p: Coo
         planet
             .events
             .start_event(PlanetEvent::Plague(plague_event), None);
+        planet
+            .reports
+            .append(planet.cycles, ReportContent::EventPlague { pos: p });
         for e in planet.events.in_progress_iter_mut() {
             if let PlanetEvent::Plague(plague_event) = &mut e.event {
                 break 'a plague_event;
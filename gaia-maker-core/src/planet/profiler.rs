@@ -0,0 +1,86 @@
+use super::*;
+
+/// Smoothing factor for exponential moving averages of stage durations
+const AVERAGE_ALPHA: f64 = 0.05;
+
+/// Holds time taken by each stage of `Planet::advance`
+#[derive(Clone, Default, Debug)]
+pub struct Profiler {
+    stages: Vec<StageTime>,
+    /// The number of measured cycles
+    pub n_cycles: u64,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct StageTime {
+    pub name: &'static str,
+    /// Time of the last cycle [ms]
+    pub last: f64,
+    /// Moving average [ms]
+    pub average: f64,
+    /// Maximum time [ms]
+    pub max: f64,
+}
+
+impl Profiler {
+    pub fn stages(&self) -> &[StageTime] {
+        &self.stages
+    }
+
+    /// Sum of moving averages of all stages [ms]
+    pub fn total_average(&self) -> f64 {
+        self.stages.iter().map(|stage| stage.average).sum()
+    }
+
+    pub fn reset(&mut self) {
+        self.stages.clear();
+        self.n_cycles = 0;
+    }
+
+    fn record(&mut self, name: &'static str, ms: f64) {
+        if let Some(stage) = self.stages.iter_mut().find(|stage| stage.name == name) {
+            stage.last = ms;
+            stage.average += AVERAGE_ALPHA * (ms - stage.average);
+            stage.max = stage.max.max(ms);
+        } else {
+            self.stages.push(StageTime {
+                name,
+                last: ms,
+                average: ms,
+                max: ms,
+            });
+        }
+    }
+}
+
+/// Measures time between stages. Does nothing on wasm because `Instant` is unavailable
+pub(super) struct StageTimer {
+    #[cfg(not(target_arch = "wasm32"))]
+    last: std::time::Instant,
+}
+
+impl StageTimer {
+    pub fn start() -> Self {
+        Self {
+            #[cfg(not(target_arch = "wasm32"))]
+            last: std::time::Instant::now(),
+        }
+    }
+
+    /// Record time elapsed since the previous lap as the given stage
+    pub fn lap(&mut self, sim: &mut Sim, name: &'static str) {
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            let now = std::time::Instant::now();
+            let ms = (now - self.last).as_secs_f64() * 1000.0;
+            self.last = now;
+            sim.profiler.record(name, ms);
+        }
+        #[cfg(target_arch = "wasm32")]
+        let _ = (sim, name);
+    }
+
+    pub fn finish(self, sim: &mut Sim) {
+        sim.profiler.n_cycles += 1;
+    }
+}
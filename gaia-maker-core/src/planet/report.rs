@@ -98,6 +98,7 @@ pub enum ReportContent {
     WarnLowTemp,
     WarnLowOxygen,
     WarnLowCarbonDioxide,
+    WarnRunawayGreenhouse,
     EventAnimalBorn {
         pos: Coords,
         animal: AnimalId,
@@ -146,6 +147,11 @@ pub enum ReportContent {
         id: AnimalId,
         name: String,
     },
+    EventPlague {
+        pos: Coords,
+    },
+    /// The journal became too long and replay is disabled
+    EventJournalDiscarded {},
 }
 
 impl ReportContent {
@@ -156,6 +162,7 @@ impl ReportContent {
                 | Self::WarnLowTemp
                 | Self::WarnLowOxygen
                 | Self::WarnLowCarbonDioxide
+                | Self::WarnRunawayGreenhouse
         )
     }
 
@@ -165,7 +172,8 @@ impl ReportContent {
             | Self::EventAchiveCivilization { pos, .. }
             | Self::EventCivilized { pos, .. }
             | Self::EventCivAdvance { pos, .. }
-            | Self::EventCivDecadence { pos, .. } => Some(*pos),
+            | Self::EventCivDecadence { pos, .. }
+            | Self::EventPlague { pos } => Some(*pos),
             _ => None,
         }
     }
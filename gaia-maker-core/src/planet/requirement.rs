@@ -95,8 +95,9 @@ impl Requirement {
                 false
             }
             Self::OrbitalMirrorAdjust { range } => {
-                if let BuildingControlValue::IncreaseRate(rate) =
-                    &planet.space_building(SpaceBuildingKind::OrbitalMirror).control
+                if let BuildingControlValue::IncreaseRate(rate) = &planet
+                    .space_building(SpaceBuildingKind::new("orbital-mirror"))
+                    .control
                 {
                     range.contains(rate)
                 } else {
@@ -38,6 +38,10 @@ impl Resources {
         self.gene_point = (self.gene_point + self.diff_gene_point).min(GENE_POINT_MAX);
     }
 
+    pub fn add_material(&mut self, value: f32) {
+        self.material = (self.material + value).min(MATERIAL_MAX);
+    }
+
     pub fn debug_max(&mut self) {
         self.material = MATERIAL_MAX;
         self.gene_point = GENE_POINT_MAX;
@@ -0,0 +1,200 @@
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+
+use geom::Direction;
+
+use super::*;
+
+/// Tolerance to compare heights [m]
+const HEIGHT_EPS: f32 = 1.0e-3;
+
+/// Drainage network of the terrain. It is recalculated only when heights or the sea level change
+#[derive(Clone, Debug)]
+pub struct Drainage {
+    heights: Vec<f32>,
+    sea_level: f32,
+    /// Heights with depressions filled up to their spill level
+    filled: Array2d<f32>,
+    /// The tile that each tile drains to
+    receiver: Array2d<Option<Coords>>,
+    /// Tiles from downstream to upstream
+    order: Vec<Coords>,
+}
+
+impl Drainage {
+    fn is_valid(&self, planet: &Planet) -> bool {
+        self.sea_level == planet.water.sea_level
+            && self
+                .heights
+                .iter()
+                .eq(planet.map.iter().map(|tile| &tile.height))
+    }
+}
+
+/// Fill depressions by priority-flood from the sea. Each tile drains to the tile it was flooded from
+fn calc_drainage(planet: &Planet, sim: &Sim) -> Drainage {
+    let size = planet.map.size();
+    let map_iter_idx = planet.map.iter_idx();
+    let coords_converter = sim.coords_converter();
+
+    let mut filled = Array2d::new(size.0, size.1, 0.0);
+    let mut receiver: Array2d<Option<Coords>> = Array2d::new(size.0, size.1, None);
+    let mut visited = Array2d::new(size.0, size.1, false);
+    let mut order = Vec::with_capacity(planet.n_tile() as usize);
+    let mut queue = BinaryHeap::new();
+
+    for p in map_iter_idx {
+        if planet.is_ocean_tile(p) {
+            filled[p] = planet.water.sea_level;
+            visited[p] = true;
+            queue.push(FloodCell(planet.water.sea_level, p));
+        }
+    }
+    // A planet without sea drains to its lowest tile
+    if queue.is_empty()
+        && let Some(p) = map_iter_idx
+            .into_iter()
+            .min_by(|a, b| planet.map[*a].height.total_cmp(&planet.map[*b].height))
+    {
+        filled[p] = planet.map[p].height;
+        visited[p] = true;
+        queue.push(FloodCell(planet.map[p].height, p));
+    }
+
+    while let Some(FloodCell(h, p)) = queue.pop() {
+        order.push(p);
+        for dir in Direction::FOUR_DIRS {
+            let Some(adjacent_tile) = coords_converter.conv(p + dir.as_coords()) else {
+                continue;
+            };
+            if visited[adjacent_tile] {
+                continue;
+            }
+            visited[adjacent_tile] = true;
+            let h_adj = planet.map[adjacent_tile].height.max(h);
+            filled[adjacent_tile] = h_adj;
+            receiver[adjacent_tile] = Some(p);
+            queue.push(FloodCell(h_adj, adjacent_tile));
+        }
+    }
+
+    Drainage {
+        heights: planet.map.iter().map(|tile| tile.height).collect(),
+        sea_level: planet.water.sea_level,
+        filled,
+        receiver,
+        order,
+    }
+}
+
+/// Calculate river flow from rainfall and form lakes in closed basins
+pub fn sim_river(planet: &mut Planet, sim: &mut Sim, params: &Params) {
+    let size = planet.map.size();
+    let map_iter_idx = planet.map.iter_idx();
+    let coords_converter = sim.coords_converter();
+
+    if !sim
+        .drainage
+        .as_ref()
+        .is_some_and(|drainage| drainage.is_valid(planet))
+    {
+        sim.drainage = Some(calc_drainage(planet, sim));
+    }
+    let Drainage {
+        filled,
+        receiver,
+        order,
+        ..
+    } = sim.drainage.as_ref().unwrap();
+
+    // Accumulate rainfall on land from upstream to downstream
+    let mut flow = Array2d::new(size.0, size.1, 0.0);
+    for &p in order.iter().rev() {
+        if !planet.is_ocean_tile(p) {
+            flow[p] += planet.map[p].rainfall;
+        }
+        if let Some(r) = receiver[p] {
+            flow[r] += flow[p];
+        }
+    }
+
+    // Depressions with enough inflow become lakes filled to their spill level
+    let mut lake_level = Array2d::new(size.0, size.1, None);
+    let mut checked = Array2d::new(size.0, size.1, false);
+    for p in map_iter_idx {
+        if checked[p] || planet.is_ocean_tile(p) || filled[p] <= planet.map[p].height + HEIGHT_EPS {
+            continue;
+        }
+        let level = filled[p];
+        let mut basin = vec![p];
+        let mut max_flow: f32 = 0.0;
+        checked[p] = true;
+        let mut i = 0;
+        while i < basin.len() {
+            let q = basin[i];
+            max_flow = max_flow.max(flow[q]);
+            for dir in Direction::FOUR_DIRS {
+                if let Some(adjacent_tile) = coords_converter.conv(q + dir.as_coords())
+                    && !checked[adjacent_tile]
+                    && !planet.is_ocean_tile(adjacent_tile)
+                    && (filled[adjacent_tile] - level).abs() < HEIGHT_EPS
+                    && planet.map[adjacent_tile].height + HEIGHT_EPS < level
+                {
+                    checked[adjacent_tile] = true;
+                    basin.push(adjacent_tile);
+                }
+            }
+            i += 1;
+        }
+        if max_flow >= params.sim.lake_flow_threshold {
+            for q in basin {
+                lake_level[q] = Some(level);
+            }
+        }
+    }
+
+    let mut lake_volume = 0.0;
+    for p in map_iter_idx {
+        let tile = &mut planet.map[p];
+        if let Some(level) = lake_level[p] {
+            lake_volume += (level - tile.height) * sim.tile_area;
+        }
+        tile.river_flow = flow[p];
+        tile.lake_level = lake_level[p];
+        if tile.lake_level.is_some() && tile.biome.is_land(params) {
+            tile.biome = Biome::OCEAN;
+            tile.sea_temp = tile.temp;
+        }
+    }
+    planet.water.lake_volume = lake_volume;
+}
+
+impl Planet {
+    /// Tile below the sea level. Lakes above the sea level are not included
+    pub fn is_ocean_tile(&self, p: Coords) -> bool {
+        self.height_above_sea_level(p) < 0.0
+    }
+
+    pub fn is_river(&self, p: Coords, params: &Params) -> bool {
+        let tile = &self.map[p];
+        tile.biome.is_land(params) && tile.river_flow >= params.sim.river_flow_threshold
+    }
+}
+
+/// Tile in the priority queue ordered by the lowest height first
+#[derive(Clone, Copy, PartialEq, Debug)]
+struct FloodCell(f32, Coords);
+
+impl Eq for FloodCell {}
+
+impl PartialOrd for FloodCell {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for FloodCell {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.0.total_cmp(&self.0)
+    }
+}
@@ -58,3 +58,29 @@ impl<'de> DeserializeAs<'de, f32> for Percent {
         Ok(v)
     }
 }
+
+/// Like `serde_with::rust::unwrap_or_skip`, writes `Option<T>` as `T` in asset files.
+/// `None` is serialized as unit, so it is also accepted when deserializing saved data.
+pub mod unwrap_or_unit {
+    use serde::{Deserialize, Deserializer};
+
+    pub use serde_with::rust::unwrap_or_skip::serialize;
+
+    pub fn deserialize<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+    where
+        D: Deserializer<'de>,
+        T: Deserialize<'de>,
+    {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Repr<T> {
+            Unit(()),
+            Value(T),
+        }
+
+        Ok(match Repr::deserialize(deserializer)? {
+            Repr::Unit(()) => None,
+            Repr::Value(value) => Some(value),
+        })
+    }
+}
@@ -3,11 +3,13 @@ use rand::distr::Distribution;
 use rand::rngs::SmallRng;
 
 use super::achivement::Achivement;
+use super::river::Drainage;
 use super::*;
 use std::collections::{BTreeMap, HashMap};
 use std::f32::consts::PI;
 
 /// Holds data for simulation
+#[derive(Clone)]
 pub struct Sim {
     /// Fast rng for simulation
     pub rng: SmallRng,
@@ -39,10 +41,24 @@ pub struct Sim {
     pub sea_heat_cap: Array2d<f32>,
     /// Tile albedo
     pub albedo: Array2d<f32>,
+    /// Tile greenhouse effect
+    pub greenhouse_effect: Array2d<f32>,
     /// Vapor in air
     pub vapor: Array2d<f32>,
     /// Vapor in air (used for calculation)
     pub vapor_new: Array2d<f32>,
+    /// Aerosol in air (used for calculation)
+    pub aerosol: Array2d<f32>,
+    /// Aerosol in air (used for calculation)
+    pub aerosol_new: Array2d<f32>,
+    /// Prevailing wind of tiles (eastward, northward)
+    pub wind: Array2d<(f32, f32)>,
+    /// Surface ocean current of tiles (eastward, northward)
+    pub ocean_current: Array2d<(f32, f32)>,
+    /// Sea or not for each tile at last ocean current calculation
+    pub sea_tiles_before: Vec<bool>,
+    /// Convergence speed with adjacent tectonic plates
+    pub plate_convergence: Array2d<f32>,
     /// Tile humidity that calculated by adjusting rainfall by temperature
     pub humidity: Array2d<f32>,
     /// Fertility value and effect to tile from structures or other factors
@@ -73,6 +89,12 @@ pub struct Sim {
     pub new_achievements: FnvHashSet<Achivement>,
     /// Animal evolution table
     pub animal_evolution_table: AnimalEvolutionTable,
+    /// Predator-prey relations between animals
+    pub food_web: FoodWeb,
+    /// Time taken by each simulation stage
+    pub profiler: Profiler,
+    /// Cached drainage network of rivers
+    pub drainage: Option<Drainage>,
 }
 
 impl Sim {
@@ -94,7 +116,7 @@ impl Sim {
         }
 
         Sim {
-            rng: misc::get_rng(),
+            rng: misc::get_seeded_rng(planet.seed, planet.cycles),
             before_start: false,
             size,
             tile_area,
@@ -109,8 +131,15 @@ impl Sim {
             stemp: Array2d::new(size.0, size.1, 0.0),
             sea_heat_cap: Array2d::new(size.0, size.1, 0.0),
             albedo: Array2d::new(size.0, size.1, 0.0),
+            greenhouse_effect: Array2d::new(size.0, size.1, 0.0),
             vapor,
             vapor_new: Array2d::new(size.0, size.1, 0.0),
+            aerosol: Array2d::new(size.0, size.1, 0.0),
+            aerosol_new: Array2d::new(size.0, size.1, 0.0),
+            wind: super::wind::calc_wind(planet, params),
+            ocean_current: Array2d::new(size.0, size.1, (0.0, 0.0)),
+            sea_tiles_before: Vec::new(),
+            plate_convergence: super::terrain::calc_plate_convergence(planet, params),
             humidity: Array2d::new(size.0, size.1, 0.0),
             fertility_value_and_effect: Array2d::new(size.0, size.1, (0.0, 0.0)),
             diff_biomass: Array2d::new(size.0, size.1, 0.0),
@@ -126,6 +155,9 @@ impl Sim {
             war_target_settlements: HashMap::default(),
             new_achievements: FnvHashSet::default(),
             animal_evolution_table: AnimalEvolutionTable::new(params),
+            food_web: FoodWeb::new(planet, params),
+            profiler: Profiler::default(),
+            drainage: None,
         }
     }
 
@@ -144,7 +176,7 @@ impl Sim {
 }
 
 #[derive(Clone, Copy, Debug)]
-pub struct CoordsConverter((u32, u32));
+pub struct CoordsConverter(pub(super) (u32, u32));
 
 impl CoordsConverter {
     pub fn new(planet: &Planet) -> Self {
@@ -156,7 +188,7 @@ impl CoordsConverter {
     }
 }
 
-#[derive(Default, Debug)]
+#[derive(Clone, Default, Debug)]
 pub struct CivSum(HashMap<AnimalId, CivSumValues>);
 
 impl CivSum {
@@ -194,7 +226,7 @@ pub struct CivSumValues {
     pub n_moving: u32,
 }
 
-#[derive(Default)]
+#[derive(Clone, Default)]
 pub struct AnimalEvolutionTable(
     HashMap<AnimalId, (Vec<AnimalId>, rand::distr::weighted::WeightedIndex<f32>)>,
 );
@@ -0,0 +1,207 @@
+use std::ops::Deref;
+use std::sync::Arc;
+
+use rand::{Rng, seq::IndexedRandom};
+
+use super::animal::calc_cap_by_atmo_temp;
+use super::*;
+
+/// Animal species generated during the game by branching from an existing species
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Species {
+    /// Generated name
+    pub name: String,
+    /// The species this species branched from
+    pub parent: AnimalId,
+    /// The species defined in assets at the root of the lineage
+    pub origin: AnimalId,
+    pub attr: Arc<AnimalAttr>,
+}
+
+impl Planet {
+    /// Attributes of the animal defined in assets or generated on this planet
+    pub fn animal_attr<'a>(&'a self, id: &AnimalId, params: &'a Params) -> &'a AnimalAttr {
+        params.animals.get(id).unwrap_or_else(|| &self.species[id].attr)
+    }
+
+    /// All animals defined in assets and generated on this planet
+    pub fn animal_list<'a>(
+        &'a self,
+        params: &'a Params,
+    ) -> impl Iterator<Item = (AnimalId, &'a AnimalAttr)> {
+        params
+            .animals
+            .iter()
+            .chain(self.species.iter().map(|(id, species)| (id, &*species.attr)))
+            .map(|(id, attr)| (*id, attr))
+    }
+
+    /// The species defined in assets that the animal originates from
+    pub fn origin_species(&self, id: AnimalId) -> AnimalId {
+        self.species.get(&id).map_or(id, |species| species.origin)
+    }
+}
+
+/// Animal attributes not borrowing the planet, to use them while modifying the planet
+pub enum DetachedAnimalAttr<'a> {
+    Params(&'a AnimalAttr),
+    Species(Arc<AnimalAttr>),
+}
+
+impl Deref for DetachedAnimalAttr<'_> {
+    type Target = AnimalAttr;
+
+    fn deref(&self) -> &AnimalAttr {
+        match self {
+            Self::Params(attr) => attr,
+            Self::Species(attr) => attr,
+        }
+    }
+}
+
+/// Attributes of the animal usable while modifying the planet, without cloning them
+pub fn detached_animal_attr<'a>(
+    planet: &Planet,
+    id: &AnimalId,
+    params: &'a Params,
+) -> DetachedAnimalAttr<'a> {
+    if let Some(attr) = params.animals.get(id) {
+        DetachedAnimalAttr::Params(attr)
+    } else {
+        DetachedAnimalAttr::Species(planet.species[id].attr.clone())
+    }
+}
+
+/// Branch the animal into a new species if it is isolated from the same species or lives in
+/// an extreme environment. Returns true if a new species is generated.
+pub fn branch_species(
+    planet: &mut Planet,
+    sim: &mut Sim,
+    p: Coords,
+    size: AnimalSize,
+    params: &Params,
+) -> bool {
+    if planet.species.len() >= params.sim.species_max {
+        return false;
+    }
+    let animal = planet.map[p].animal[size as usize].expect("no animal to branch");
+    let attr = planet.animal_attr(&animal.id, params);
+
+    let isolated = !geom::CHEBYSHEV_DISTANCE_1_COORDS
+        .iter()
+        .chain(geom::CHEBYSHEV_DISTANCE_2_COORDS)
+        .any(|&d| {
+            sim.convert_p_cyclic(p + d).is_some_and(|p_adj| {
+                planet.map[p_adj].animal[size as usize].is_some_and(|other| other.id == animal.id)
+            })
+        });
+    let extreme =
+        calc_cap_by_atmo_temp(planet, p, attr, params, 0.0) < params.sim.species_extreme_livability;
+    if !isolated && !extreme {
+        return false;
+    }
+
+    let prob = params.sim.species_branch_prob * (planet.state.animal_evolution as f32 / 100.0);
+    if !sim.rng.random_bool(prob.into()) {
+        return false;
+    }
+
+    let species = new_species(planet, sim, p, animal.id, params);
+    let id = AnimalId::from(&format!("species-{}", planet.species.len() + 1)).unwrap();
+    planet.species.insert(id, species);
+    planet.map[p].animal[size as usize] = Some(Animal {
+        id,
+        n: animal.n,
+        evo_exp: 0.0,
+    });
+    sim.food_web = FoodWeb::new(planet, params);
+    planet.reports.append(
+        planet.cycles,
+        ReportContent::EventAnimalBorn { animal: id, pos: p },
+    );
+
+    true
+}
+
+fn new_species(
+    planet: &Planet,
+    sim: &mut Sim,
+    p: Coords,
+    parent: AnimalId,
+    params: &Params,
+) -> Species {
+    let mut attr = planet.animal_attr(&parent, params).clone();
+    let ratio = params.sim.species_mutation_ratio;
+    let mut mutate = |value: f32| value * sim.rng.random_range((1.0 - ratio)..=(1.0 + ratio));
+
+    // Shift the livable temperature range toward the temperature of the tile
+    let (min, max) = attr.temp;
+    let center = (min + max) / 2.0;
+    let shift = (planet.map[p].temp - center).clamp(
+        -params.sim.species_temp_shift_max,
+        params.sim.species_temp_shift_max,
+    );
+    let half_width = mutate((max - min) / 2.0);
+    attr.temp = (center + shift - half_width, center + shift + half_width);
+
+    // Growth speed is mutated relative to the origin species not to drift over generations.
+    // The parent is used instead if the origin is removed from assets
+    let origin = planet.origin_species(parent);
+    let origin_growth_speed = params
+        .animals
+        .get(&origin)
+        .map_or(attr.growth_speed, |origin_attr| origin_attr.growth_speed);
+    let relative_growth_speed = mutate(attr.growth_speed / origin_growth_speed);
+    attr.growth_speed = origin_growth_speed * relative_growth_speed.clamp(1.0 - ratio, 1.0 + ratio);
+    attr.civ_prob = mutate(attr.civ_prob).clamp(0.0, 1.0);
+    attr.evolve_from = Vec::new();
+
+    // Extend the habitat to a biome in the surrounding tiles
+    if sim.rng.random_bool(params.sim.species_habitat_mutation_prob) {
+        let candidates: Vec<Biome> = geom::CHEBYSHEV_DISTANCE_1_COORDS
+            .iter()
+            .filter_map(|&d| sim.convert_p_cyclic(p + d))
+            .map(|p_adj| planet.map[p_adj].biome)
+            .filter(|biome| !attr.habitat.match_biome(*biome, params))
+            .collect();
+        if let Some(new_biome) = candidates.choose(&mut sim.rng) {
+            let mut biomes: Vec<Biome> = params
+                .biome_list()
+                .into_iter()
+                .filter(|biome| attr.habitat.match_biome(*biome, params))
+                .collect();
+            biomes.push(*new_biome);
+            attr.habitat = AnimalHabitat::Biomes(biomes);
+        }
+    }
+
+    attr.color = attr
+        .color
+        .map(|c| c.saturating_add_signed(sim.rng.random_range(-32..=32)));
+
+    Species {
+        name: species_name(sim, params),
+        parent,
+        origin,
+        attr: Arc::new(attr),
+    }
+}
+
+fn species_name(sim: &mut Sim, params: &Params) -> String {
+    let n = sim.rng.random_range(2..=3);
+    let name: String = (0..n)
+        .map(|_| {
+            params
+                .sim
+                .species_name_syllables
+                .choose(&mut sim.rng)
+                .unwrap()
+                .as_str()
+        })
+        .collect();
+    let mut chars = name.chars();
+    chars
+        .next()
+        .map(|c| c.to_uppercase().chain(chars).collect())
+        .unwrap_or_default()
+}
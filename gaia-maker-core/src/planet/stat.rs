@@ -14,6 +14,12 @@ pub struct Stat {
     history: VecDeque<Record>,
     #[serde(default)]
     pub animals: HashMap<AnimalId, f32>,
+    /// Min and max of the average air temperature in the last year
+    #[serde(default)]
+    last_year_air_temp_range: Option<(f32, f32)>,
+    /// Year and min and max of the average air temperature in the current year
+    #[serde(default)]
+    current_year_air_temp_range: Option<(u64, f32, f32)>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -27,6 +33,10 @@ pub struct Record {
     pub p_n2: f32,
     pub p_co2: f32,
     pub pop: fnv::FnvHashMap<AnimalId, f32>,
+    #[serde(default)]
+    pub p_ch4: f32,
+    #[serde(default)]
+    pub ocean_carbon: f32,
 }
 
 impl Stat {
@@ -39,6 +49,30 @@ impl Stat {
             sum_buried_carbon: 0.0,
             animals: HashMap::default(),
             history: VecDeque::with_capacity(params.history.max_record + 1),
+            last_year_air_temp_range: None,
+            current_year_air_temp_range: None,
+        }
+    }
+
+    /// Min and max of the average air temperature through seasons
+    pub fn seasonal_air_temp_range(&self) -> (f32, f32) {
+        self.last_year_air_temp_range
+            .or(self.current_year_air_temp_range.map(|(_, min, max)| (min, max)))
+            .unwrap_or((self.average_air_temp, self.average_air_temp))
+    }
+
+    pub(super) fn record_air_temp_range(&mut self, year: u64, temp: f32) {
+        match &mut self.current_year_air_temp_range {
+            Some((current_year, current_min, current_max)) if *current_year == year => {
+                *current_min = current_min.min(temp);
+                *current_max = current_max.max(temp);
+            }
+            current => {
+                if let Some((_, last_min, last_max)) = *current {
+                    self.last_year_air_temp_range = Some((last_min, last_max));
+                }
+                *current = Some((year, temp, temp));
+            }
         }
     }
 
@@ -78,6 +112,8 @@ pub fn record_stats(planet: &mut Planet, params: &Params) {
         p_n2: planet.atmo.partial_pressure(GasKind::Nitrogen),
         p_co2: planet.atmo.partial_pressure(GasKind::CarbonDioxide),
         pop,
+        p_ch4: planet.atmo.partial_pressure(GasKind::Methane),
+        ocean_carbon: planet.water.dissolved_carbon,
     };
 
     planet.stat.history.push_front(record);
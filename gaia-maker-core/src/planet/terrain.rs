@@ -0,0 +1,107 @@
+use geom::Direction;
+use sim::CoordsConverter;
+
+use super::*;
+
+/// Maximum ratio of height difference to the lower adjacent tile eroded at once
+const MAX_EROSION_RATIO: f32 = 0.25;
+
+/// Erode terrain by rainfall and uplift it at converging plate boundaries
+pub fn sim_terrain(planet: &mut Planet, sim: &mut Sim, params: &Params) {
+    if planet.cycles % params.sim.terrain_sim_interval_cycles != 0 {
+        return;
+    }
+
+    let size = planet.map.size();
+    let map_iter_idx = planet.map.iter_idx();
+    let coords_converter = sim.coords_converter();
+    let mut diff = Array2d::new(size.0, size.1, 0.0);
+
+    // Water flowing on land carries sediment to the steepest downhill tile
+    for p in map_iter_idx {
+        if planet.is_ocean_tile(p) {
+            continue;
+        }
+        let h = planet.map[p].height;
+        let Some(p_low) = Direction::FOUR_DIRS
+            .into_iter()
+            .filter_map(|dir| coords_converter.conv(p + dir.as_coords()))
+            .min_by(|a, b| planet.map[*a].height.total_cmp(&planet.map[*b].height))
+        else {
+            continue;
+        };
+        let d = h - planet.map[p_low].height;
+        if d <= 0.0 {
+            continue;
+        }
+        let water = planet.map[p].river_flow.max(planet.map[p].rainfall);
+        let erosion = (params.sim.erosion_factor * water.sqrt() * d).min(MAX_EROSION_RATIO * d);
+        diff[p] -= erosion;
+        diff[p_low] += erosion;
+    }
+
+    for p in map_iter_idx {
+        // Uplift slows down as mountains approach the height balanced by erosion and isostasy
+        let height_ratio = planet.height_above_sea_level(p) / params.sim.max_tectonic_height;
+        let uplift = params.sim.tectonic_uplift
+            * sim.plate_convergence[p]
+            * (1.0 - height_ratio).clamp(0.0, 1.0);
+        let tile = &mut planet.map[p];
+        tile.height = (tile.height + diff[p] + uplift).max(0.0);
+    }
+
+    super::water::update_sea_level(planet, sim, params);
+}
+
+/// Split the map into plates moving in random directions, and calculate how fast each tile
+/// converges with adjacent plates
+pub fn calc_plate_convergence(planet: &Planet, params: &Params) -> Array2d<f32> {
+    let (w, h) = planet.map.size();
+    let mut convergence = Array2d::new(w, h, 0.0);
+    if params.sim.n_tectonic_plates == 0 {
+        return convergence;
+    }
+
+    let mut rng = misc::get_seeded_rng(planet.seed, misc::RNG_STREAM_PLATES);
+    let plates: Vec<(Coords, (f32, f32))> = (0..params.sim.n_tectonic_plates)
+        .map(|_| {
+            let center = Coords::new(rng.random_range(0..w) as i32, rng.random_range(0..h) as i32);
+            let angle = rng.random_range(0.0..(2.0 * PI));
+            (center, (angle.cos(), angle.sin()))
+        })
+        .collect();
+
+    let mut plate_of_tile = Array2d::new(w, h, 0);
+    for p in planet.map.iter_idx() {
+        plate_of_tile[p] = plates
+            .iter()
+            .enumerate()
+            .min_by_key(|(_, (center, _))| {
+                let dx = (p.0 - center.0).abs();
+                let dx = dx.min(w as i32 - dx);
+                let dy = p.1 - center.1;
+                dx * dx + dy * dy
+            })
+            .map(|(i, _)| i)
+            .unwrap();
+    }
+
+    let coords_converter = CoordsConverter::new(planet);
+    for p in planet.map.iter_idx() {
+        let v = plates[plate_of_tile[p]].1;
+        for dir in Direction::FOUR_DIRS {
+            let d = dir.as_coords();
+            let Some(p_adj) = coords_converter.conv(p + d) else {
+                continue;
+            };
+            if plate_of_tile[p_adj] == plate_of_tile[p] {
+                continue;
+            }
+            let v_adj = plates[plate_of_tile[p_adj]].1;
+            let approach = (v.0 - v_adj.0) * d.0 as f32 + (v.1 - v_adj.1) * d.1 as f32;
+            convergence[p] += approach.max(0.0);
+        }
+    }
+
+    convergence
+}
@@ -42,8 +42,10 @@ pub fn advance(planet: &mut Planet, sim: &mut Sim, params: &Params) {
     super::geological_event::advance_geological_event(planet, sim, params);
 
     sim.war_counter.clear();
+    let mut aerosol_emissions = Vec::new();
 
-    for tile in planet.map.iter_mut() {
+    for p in planet.map.iter_idx() {
+        let tile = &mut planet.map[p];
         let tile_events = &mut tile.tile_events;
         if tile_events.0.is_empty() {
             continue;
@@ -66,7 +68,7 @@ pub fn advance(planet: &mut Planet, sim: &mut Sim, params: &Params) {
             if biomass <= extinction_biomass {
                 tile_events.remove(TileEventKind::Fire);
             }
-            planet.atmo.aerosol += params.event.fire_aerosol;
+            aerosol_emissions.push((p, params.event.fire_aerosol));
 
             if matches!(tile.structure, Some(Structure::Settlement(_))) {
                 tile.structure = None;
@@ -94,7 +96,7 @@ pub fn advance(planet: &mut Planet, sim: &mut Sim, params: &Params) {
             if *remaining_cycles == 0 {
                 tile_events.remove(TileEventKind::AerosolInjection);
             }
-            planet.atmo.aerosol += params.event.aerosol_injection_amount;
+            aerosol_emissions.push((p, params.event.aerosol_injection_amount));
         }
 
         if let Some(TileEvent::SolarRay { remaining_cycles }) =
@@ -113,9 +115,9 @@ pub fn advance(planet: &mut Planet, sim: &mut Sim, params: &Params) {
             let burned_biomass = sim.biomass_density_to_mass() * tile.biomass;
             tile.biomass = 0.0;
             planet.atmo.release_carbon(burned_biomass);
-            planet.atmo.aerosol += params.event.solar_ray_aerosol;
+            aerosol_emissions.push((p, params.event.solar_ray_aerosol));
 
-            if tile.biome.is_land() {
+            if tile.biome.is_land(params) {
                 let d = sim
                     .rng
                     .sample(ConstantDist::from(params.event.solar_ray_digging));
@@ -129,10 +131,10 @@ pub fn advance(planet: &mut Planet, sim: &mut Sim, params: &Params) {
                     params.event.solar_ray_buried_carbon_burn_ratio * tile.buried_carbon;
                 tile.buried_carbon -= burned_buried_carbon;
                 planet.atmo.release_carbon(burned_buried_carbon);
-                tile.biome = Biome::Rock;
+                tile.biome = Biome::ROCK;
             } else {
                 planet.water.water_volume -= params.event.solar_ray_ocean_vaporization;
-                tile.biome = Biome::Ocean;
+                tile.biome = Biome::OCEAN;
             }
         }
 
@@ -165,7 +167,9 @@ pub fn advance(planet: &mut Planet, sim: &mut Sim, params: &Params) {
             tile.biomass = biomass;
             let burned_biomass = sim.biomass_density_to_mass();
             planet.atmo.release_carbon(burned_biomass);
-            planet.atmo.aerosol += params.event.nuclear_explosion_aerosol;
+            aerosol_emissions.push((p, params.event.nuclear_explosion_aerosol));
+            planet.atmo.ozone_damage =
+                (planet.atmo.ozone_damage + params.event.nuclear_explosion_ozone_damage).min(1.0);
 
             if matches!(tile.structure, Some(Structure::Settlement(_))) {
                 tile.structure = None;
@@ -178,6 +182,10 @@ pub fn advance(planet: &mut Planet, sim: &mut Sim, params: &Params) {
         }
     }
 
+    for (p, amount) in aerosol_emissions {
+        super::cloud::emit_aerosol(planet, sim, p, amount, params);
+    }
+
     advance_vehicle(planet, sim, params);
     super::war::advance_troops(planet, sim, params);
 }
@@ -261,11 +269,11 @@ fn advance_vehicle(planet: &mut Planet, sim: &mut Sim, params: &Params) {
             continue;
         }
 
-        let animal_attr = &params.animals[&id];
+        let animal_attr = &super::species::detached_animal_attr(planet, &id, params);
         let civ_sum_values = sim.civ_sum.get_mut(id);
 
-        if !animal_attr.habitat.match_biome(planet.map[p].biome) {
-            if kind == VehicleKind::Ship && planet.map[p].biome != Biome::Ocean {
+        if !animal_attr.habitat.match_biome(planet.map[p].biome, params) {
+            if kind == VehicleKind::Ship && !planet.map[p].biome.is_open_sea(params) {
                 continue;
             }
 
@@ -279,7 +279,13 @@ fn update_target_settlements(planet: &Planet, sim: &mut Sim, params: &Params) {
                         / params.sim.buried_carbon_energy_threshold)
                         .min(2.0);
                 }
-                if matches!(tile.structure, Some(Structure::GiftTower)) {
+                if let Some(Structure::Building(kind)) = tile.structure
+                    && params
+                        .building_attrs(kind)
+                        .effects
+                        .iter()
+                        .any(|effect| matches!(effect, BuildingEffect::SupplyEnergy { .. }))
+                {
                     energy_score += 1000.0;
                 }
             }
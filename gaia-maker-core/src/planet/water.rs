@@ -1,9 +1,17 @@
 use super::misc::{bisection, linear_interpolation};
+use super::wind::advection;
 use super::*;
 use geom::Direction;
 use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
 
+/// Water mass per volume [Mt/m^3]
+const WATER_MASS_PER_VOLUME: f32 = 1.0e-6;
+/// Boiling point of water at 1 atm [K]
+const WATER_BOILING_POINT: f32 = 373.15;
+/// Latent heat of vaporization of water divided by the gas constant [K]
+const WATER_VAPORIZATION_HEAT_PER_R: f32 = 4895.0;
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Water {
     /// Volume of water including ice [m^3]
@@ -12,6 +20,21 @@ pub struct Water {
     pub sea_level: f32,
     /// Volume of ice [m^3]
     pub ice_volume: f32,
+    /// Volume of water escaped to space in the last cycle [m^3]
+    #[serde(default)]
+    pub escape_rate: f32,
+    /// Carbon mass dissolved in the ocean [Mt]
+    #[serde(default)]
+    pub dissolved_carbon: f32,
+    /// Carbon mass removed from the atmosphere by weathering in the last cycle [Mt]
+    #[serde(default)]
+    pub weathering_rate: f32,
+    /// Volume of water in lakes above the sea level [m^3]
+    #[serde(default)]
+    pub lake_volume: f32,
+    /// Carbon mass deposited as carbonate sediments on the sea floor [Mt]
+    #[serde(default)]
+    pub sediment_carbon: f32,
 }
 
 impl Water {
@@ -20,33 +43,74 @@ impl Water {
             water_volume: start_params.water_volume,
             sea_level: 0.0,
             ice_volume: 0.0,
+            escape_rate: 0.0,
+            dissolved_carbon: 0.0,
+            weathering_rate: 0.0,
+            lake_volume: 0.0,
+            sediment_carbon: 0.0,
         }
     }
 
     pub fn sea_water_volume(&self) -> f32 {
-        let v = self.water_volume - self.ice_volume;
+        let v = self.water_volume - self.ice_volume - self.lake_volume;
         if v > 0.0 { v } else { 0.0 }
     }
 }
 
 pub fn sim_water(planet: &mut Planet, sim: &mut Sim, params: &Params) {
+    exchange_atmo_vapor(planet, params);
     update_sea_level(planet, sim, params);
     advance_rainfall_calc(planet, sim, params);
+    super::river::sim_river(planet, sim, params);
     snow_calc(planet, sim, params);
 }
 
+/// Sea water evaporates into the atmosphere up to the saturation vapor pressure.
+/// When the sea boils, it vaporizes until the atmospheric pressure reaches its saturation vapor pressure
+fn exchange_atmo_vapor(planet: &mut Planet, params: &Params) {
+    let other_gases_atm = planet.atmo.atm() - planet.atmo.partial_pressure(GasKind::WaterVapor);
+    let target_atm = (saturation_vapor_pressure(planet.stat.average_air_temp)
+        * params.sim.atmo_relative_humidity)
+        .max(saturation_vapor_pressure(planet.stat.average_sea_temp) - other_gases_atm);
+    let target =
+        target_atm * params.sim.mol_per_atm * super::atmo::molecular_weight(GasKind::WaterVapor);
+    let rate = if planet.is_ocean_boiling() {
+        params.sim.ocean_boiling_rate
+    } else {
+        params.sim.atmo_vapor_exchange_rate
+    };
+    let evaporated = (rate * (target - planet.atmo.mass(GasKind::WaterVapor)))
+        .min(planet.water.sea_water_volume() * WATER_MASS_PER_VOLUME);
+    planet.atmo.add(GasKind::WaterVapor, evaporated);
+    planet.water.water_volume -= evaporated / WATER_MASS_PER_VOLUME;
+}
+
+/// Saturation vapor pressure of water by Clausius-Clapeyron relation [atm]
+pub fn saturation_vapor_pressure(temp: f32) -> f32 {
+    (WATER_VAPORIZATION_HEAT_PER_R * (1.0 / WATER_BOILING_POINT - 1.0 / temp)).exp()
+}
+
+impl Planet {
+    /// The sea boils if its saturation vapor pressure exceeds the atmospheric pressure
+    pub fn is_ocean_boiling(&self) -> bool {
+        self.water.sea_water_volume() > 0.0
+            && saturation_vapor_pressure(self.stat.average_sea_temp) > self.atmo.atm()
+    }
+}
+
 pub fn update_sea_level(planet: &mut Planet, sim: &Sim, params: &Params) {
     planet.water.sea_level = bisection(|x| target_function(planet, sim, x), 0.0, 10000.0, 10, 10.0);
 
     for p in planet.map.iter_idx() {
+        let is_ocean_tile = planet.is_ocean_tile(p);
         let tile = &mut planet.map[p];
 
-        if tile.height < planet.water.sea_level && tile.biome.is_land() {
-            tile.biome = Biome::Ocean;
+        if is_ocean_tile && tile.biome.is_land(params) {
+            tile.biome = Biome::OCEAN;
             tile.sea_temp = tile.temp;
-        } else if tile.height >= planet.water.sea_level && tile.biome.is_sea() {
+        } else if !is_ocean_tile && tile.biome.is_sea(params) && tile.lake_level.is_none() {
             tile.fertility *= params.sim.change_from_ocean_fertility_factor;
-            tile.biome = Biome::Rock;
+            tile.biome = Biome::ROCK;
         }
     }
 }
@@ -76,13 +140,23 @@ pub fn advance_rainfall_calc(planet: &mut Planet, sim: &mut Sim, params: &Params
         par_iter.for_each(|(i, vapor_new)| {
             let p = Coords::from_index_size(i, size);
             let biome = planet.map[p].biome;
-            let building_effect = planet.working_building_effect(p, params);
+            let building_vapor =
+                planet
+                    .working_building_effects(p, params)
+                    .iter()
+                    .find_map(|effect| {
+                        if let BuildingEffect::Vapor { value } = effect {
+                            Some(*value)
+                        } else {
+                            None
+                        }
+                    });
 
-            if biome == Biome::Ocean {
+            if biome.is_open_sea(params) {
                 *vapor_new =
                     linear_interpolation(&params.sim.ocean_vaporization_table, planet.map[p].temp)
                         / RAINFALL_DURATION;
-            } else if let Some(BuildingEffect::Vapor { value }) = building_effect {
+            } else if let Some(value) = building_vapor {
                 *vapor_new = value / RAINFALL_DURATION;
             } else {
                 let adjacent_tile_flow: f32 = Direction::FOUR_DIRS
@@ -102,10 +176,19 @@ pub fn advance_rainfall_calc(planet: &mut Planet, sim: &mut Sim, params: &Params
                         }
                     })
                     .sum();
+                // Vapor carried by wind decreases when climbing mountains
+                let wind_flow =
+                    advection(&sim.wind, &sim.vapor, p, &coords_converter, |from, to| {
+                        let diff_height =
+                            (planet.map[to].height - planet.map[from].height).max(0.0);
+                        params.sim.wind_vapor_advection_factor
+                            / (1.0
+                                + diff_height * params.sim.coeff_vapor_diffusion_adjust_by_h_diff)
+                    });
                 let loss = sim.vapor[p]
                     * params.sim.vapor_loss_ratio
                     * (1.0 - params.biomes[&biome].revaporization_ratio);
-                *vapor_new = sim.vapor[p] + adjacent_tile_flow - loss;
+                *vapor_new = sim.vapor[p] + adjacent_tile_flow + wind_flow - loss;
             }
         });
         std::mem::swap(&mut sim.vapor, &mut sim.vapor_new);
@@ -120,7 +203,13 @@ pub fn advance_rainfall_calc(planet: &mut Planet, sim: &mut Sim, params: &Params
         planet.map[p].rainfall = rainfall;
         sum_rainfall += rainfall as f64;
         let temp = (planet.map[p].temp - KELVIN_CELSIUS).max(0.0);
-        sim.humidity[p] = (rainfall
+        // Rivers supply water to the tiles along them
+        let river_water = if planet.is_river(p, params) {
+            params.sim.river_humidity_bonus
+        } else {
+            0.0
+        };
+        sim.humidity[p] = (rainfall + river_water
             - params.sim.drying_factors.0 * (temp - params.sim.drying_factors.1))
             .max(0.0);
     }
@@ -0,0 +1,106 @@
+use std::f32::consts::{FRAC_PI_2, PI};
+
+use geom::Direction;
+
+use super::sim::CoordsConverter;
+use super::*;
+
+/// Latitude of the boundaries between Hadley, Ferrel and polar cells [rad]
+const CELL_BOUNDARIES: [f32; 2] = [PI / 6.0, PI / 3.0];
+
+/// Calculate prevailing surface wind at the latitude by three-cell circulation model.
+/// Returns eastward and northward components normalized to the range -1.0 ~ 1.0
+pub fn prevailing_wind(latitude: f32, retrograde: bool, params: &Params) -> (f32, f32) {
+    let abs_latitude = latitude.abs().min(FRAC_PI_2);
+    // Position in the cell from its equator-side boundary (0.0 ~ 1.0)
+    let (cell, lower, upper) = if abs_latitude < CELL_BOUNDARIES[0] {
+        (0, 0.0, CELL_BOUNDARIES[0])
+    } else if abs_latitude < CELL_BOUNDARIES[1] {
+        (1, CELL_BOUNDARIES[0], CELL_BOUNDARIES[1])
+    } else {
+        (2, CELL_BOUNDARIES[1], FRAC_PI_2)
+    };
+    let s = (PI * (abs_latitude - lower) / (upper - lower)).sin();
+
+    // Trade winds and polar easterlies blow to the west and equatorward, westerlies blow to the east and poleward
+    let (eastward, poleward) = match cell {
+        0 => (-s, -s),
+        1 => (s, s),
+        _ => (-0.5 * s, -0.5 * s),
+    };
+    let eastward = if retrograde { -eastward } else { eastward };
+    let northward = params.sim.meridional_wind_ratio * poleward * latitude.signum();
+    (eastward, northward)
+}
+
+/// Calculate the net advective flux of the field into the tile by the upwind scheme.
+/// The flow across each edge uses the average velocity of the two tiles, so the field is conserved.
+/// `factor` is called with the source and destination tiles of the flow
+pub fn advection(
+    velocity: &Array2d<(f32, f32)>,
+    field: &Array2d<f32>,
+    p: Coords,
+    coords_converter: &CoordsConverter,
+    factor: impl Fn(Coords, Coords) -> f32,
+) -> f32 {
+    Direction::FOUR_DIRS
+        .into_iter()
+        .map(|dir| {
+            let Some(adjacent_tile) = coords_converter.conv(p + dir.as_coords()) else {
+                return 0.0;
+            };
+            let d = dir.as_coords();
+            // Velocity toward the adjacent tile. Northward is the positive y direction
+            let speed =
+                |(eastward, northward): (f32, f32)| eastward * d.0 as f32 + northward * d.1 as f32;
+            let speed = 0.5 * (speed(velocity[p]) + speed(velocity[adjacent_tile]));
+            let (from, to) = if speed > 0.0 {
+                (p, adjacent_tile)
+            } else {
+                (adjacent_tile, p)
+            };
+            let factor = factor(from, to);
+            if factor <= 0.0 {
+                return 0.0;
+            }
+            // The flow is balanced by a return flow that carries the field of the destination tile back
+            let flow = speed.abs() * factor * (field[from] - field[to]);
+            if from == p { -flow } else { flow }
+        })
+        .sum()
+}
+
+pub fn calc_wind(planet: &Planet, params: &Params) -> Array2d<(f32, f32)> {
+    let size = planet.map.size();
+    let mut wind = Array2d::new(size.0, size.1, (0.0, 0.0));
+    for p in planet.map.iter_idx() {
+        let latitude = planet.calc_longitude_latitude(p).1;
+        wind[p] = prevailing_wind(latitude, planet.basics.retrograde_rotation, params);
+    }
+    wind
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn advection_to_north() {
+        let coords_converter = CoordsConverter((4, 4));
+        let velocity = Array2d::new(4, 4, (0.0, 1.0));
+        let mut field = Array2d::new(4, 4, 0.0);
+        let p_south = Coords(1, 1);
+        let p_north = Coords(1, 2);
+        field[p_south] = 1.0;
+
+        // Only the flow from the southern tile is allowed, so the upwind tile must be detected
+        let flux = |p| {
+            advection(&velocity, &field, p, &coords_converter, |from, _| {
+                if from == p_south { 1.0 } else { 0.0 }
+            })
+        };
+        assert!(flux(p_north) > 0.0);
+        assert!(flux(p_south) < 0.0);
+        assert_eq!(flux(Coords(1, 0)), 0.0);
+    }
+}
@@ -0,0 +1,135 @@
+use std::io::Read;
+
+use anyhow::Result;
+use byteorder::ReadBytesExt;
+use bytes::BufMut;
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+
+use crate::planet::Planet;
+
+pub const SAVE_FILE_EXTENSION: &str = "planet";
+
+const GAME_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Debug, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct SavedTime(String);
+
+/// Save file consists of a header, metadata `M` and serialized planet data
+pub struct SaveFile<M> {
+    pub version: String,
+    pub time: SavedTime,
+    pub name: String,
+    pub metadata: M,
+    pub planet_data: Vec<u8>,
+}
+
+impl<M> SaveFile<M> {
+    pub fn new(planet: &Planet, metadata: M) -> Result<Self> {
+        Ok(Self {
+            version: GAME_VERSION.into(),
+            time: SavedTime::now(),
+            name: planet.basics.name.clone(),
+            metadata,
+            planet_data: rmp_serde::to_vec(planet)?,
+        })
+    }
+
+    /// Deserialize the planet in this file
+    pub fn planet(&self) -> Result<Planet> {
+        Ok(rmp_serde::from_slice(&self.planet_data)?)
+    }
+}
+
+impl<M: Serialize> SaveFile<M> {
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let metadata = rmp_serde::to_vec_named(&self.metadata).unwrap();
+        let mut buf = Vec::new();
+
+        buf.put_u8(self.version.len().try_into().unwrap());
+        buf.put(self.version.as_bytes());
+        buf.put_u8(self.time.0.len().try_into().unwrap());
+        buf.put(self.time.0.as_bytes());
+        buf.put_u8(self.name.len().try_into().unwrap());
+        buf.put(self.name.as_bytes());
+        buf.put_u16(metadata.len().try_into().unwrap());
+        buf.put(&metadata[..]);
+        buf.put(&self.planet_data[..]);
+
+        buf
+    }
+}
+
+impl<M: DeserializeOwned + Default> SaveFile<M> {
+    /// Read a save file. Planet data is not read if `header_only` is true
+    pub fn from_reader<R: Read>(mut reader: R, header_only: bool) -> Result<Self> {
+        let len = reader.read_u8()?;
+        let mut version = vec![0; len as usize];
+        reader.read_exact(&mut version)?;
+        let version = String::from_utf8(version)?;
+
+        let len = reader.read_u8()?;
+        let mut time = vec![0; len as usize];
+        reader.read_exact(&mut time)?;
+        let time = SavedTime(String::from_utf8(time)?);
+
+        let len = reader.read_u8()?;
+        let mut name = vec![0; len as usize];
+        reader.read_exact(&mut name)?;
+        let name = String::from_utf8(name)?;
+
+        let len = reader.read_u16::<byteorder::BigEndian>()?;
+        let mut metadata = vec![0; len as usize];
+        reader.read_exact(&mut metadata)?;
+
+        let planet_data = if header_only {
+            Vec::new()
+        } else {
+            let mut planet_data = Vec::new();
+            reader.read_to_end(&mut planet_data)?;
+            planet_data
+        };
+
+        let metadata = match rmp_serde::from_slice(&metadata) {
+            Ok(metadata) => metadata,
+            Err(e) => {
+                log::warn!("invalid metadata: {}", e);
+                M::default()
+            }
+        };
+
+        Ok(Self {
+            version,
+            time,
+            name,
+            metadata,
+            planet_data,
+        })
+    }
+}
+
+impl SavedTime {
+    pub fn now() -> Self {
+        let time = chrono::Local::now().to_string();
+        Self(time.split_once('.').unwrap().0.into()) // Get "YYYY-MM-DD hh:mm:ss"
+    }
+}
+
+impl std::fmt::Display for SavedTime {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl From<std::time::SystemTime> for SavedTime {
+    fn from(value: std::time::SystemTime) -> Self {
+        let time: chrono::DateTime<chrono::Local> = value.into();
+        Self::from(time)
+    }
+}
+
+impl From<chrono::DateTime<chrono::Local>> for SavedTime {
+    fn from(value: chrono::DateTime<chrono::Local>) -> Self {
+        Self(value.format("%Y-%m-%d %H:%M:%S").to_string())
+    }
+}
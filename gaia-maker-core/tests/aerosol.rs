@@ -0,0 +1,100 @@
+//! Regression test for aerosol emitted by tile events.
+//!
+//! Aerosol injection used to raise the global aerosol amount directly. Now it is emitted to
+//! the tiles around the event, and the global effect should stay close to the former one.
+
+use std::path::{Path, PathBuf};
+
+use gaia_maker_core::planet::*;
+use tile_geom::Coords;
+
+const SEED: u64 = 1;
+const WARMUP_CYCLES: u64 = 20;
+const CYCLES: u64 = 200;
+/// Allowed ratio between the cloud albedo increase by local emission and by global emission
+const ALBEDO_RATIO_RANGE: (f32, f32) = (0.9, 1.1);
+/// Allowed difference of the average air temperature between local and global emission [K]
+const TEMP_TOLERANCE: f32 = 0.1;
+
+fn assets_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("../assets")
+}
+
+/// Returns the average cloud albedo and air temperature during the cycles
+fn advance<F: FnMut(u64, &mut Planet)>(
+    planet: &mut Planet,
+    sim: &mut Sim,
+    params: &Params,
+    mut f: F,
+) -> (f32, f32) {
+    let mut sum_albedo = 0.0;
+    let mut sum_temp = 0.0;
+    for i in 0..CYCLES {
+        f(i, planet);
+        planet.advance(sim, params);
+        sum_albedo += planet.cloud_albedo(params);
+        sum_temp += planet.stat.average_air_temp;
+    }
+    (sum_albedo / CYCLES as f32, sum_temp / CYCLES as f32)
+}
+
+#[test]
+fn aerosol_injection() {
+    let params = gaia_maker_core::loader::load_params(&assets_dir()).unwrap();
+    let start_params = start_planet_to_start_params("continental", &params, Some(SEED));
+    let mut planet = Planet::new(&start_params, &params);
+    let mut sim = Sim::new(&planet, &params);
+    planet.update(&mut sim, &params);
+    for _ in 0..WARMUP_CYCLES {
+        planet.advance(&mut sim, &params);
+    }
+    planet.res.material = 1.0e+6;
+
+    // Without aerosol injection
+    let mut base_planet = planet.clone();
+    let mut base_sim = sim.clone();
+    base_planet.update(&mut base_sim, &params);
+    let (base_albedo, _) = advance(&mut base_planet, &mut base_sim, &params, |_, _| ());
+
+    // Aerosol injection at a tile
+    let mut local_planet = planet.clone();
+    let mut local_sim = sim.clone();
+    let (w, h) = local_planet.map.size();
+    let p = Coords(w as i32 / 2, h as i32 / 2);
+    assert!(local_planet.cause_tile_event(
+        p,
+        TileEventKind::AerosolInjection,
+        &mut local_sim,
+        &params
+    ));
+    let (local_albedo, local_temp) = advance(&mut local_planet, &mut local_sim, &params, |_, _| ());
+
+    // The same amount of aerosol added to all tiles, as the former global aerosol
+    let mut global_planet = planet.clone();
+    let mut global_sim = sim.clone();
+    global_planet.update(&mut global_sim, &params);
+    let (global_albedo, global_temp) =
+        advance(&mut global_planet, &mut global_sim, &params, |i, planet| {
+            if i < params.event.aerosol_injection_cycles as u64 {
+                for tile in planet.map.iter_mut() {
+                    tile.aerosol += params.event.aerosol_injection_amount;
+                }
+            }
+        });
+
+    let local_increase = local_albedo - base_albedo;
+    let global_increase = global_albedo - base_albedo;
+    assert!(
+        global_increase > 0.0,
+        "no cloud albedo increase by global aerosol: {global_increase}"
+    );
+    let ratio = local_increase / global_increase;
+    assert!(
+        (ALBEDO_RATIO_RANGE.0..=ALBEDO_RATIO_RANGE.1).contains(&ratio),
+        "cloud albedo increase by aerosol injection {local_increase} differs from global emission {global_increase}"
+    );
+    assert!(
+        (local_temp - global_temp).abs() <= TEMP_TOLERANCE,
+        "air temperature by aerosol injection {local_temp} differs from global emission {global_temp}"
+    );
+}
@@ -0,0 +1,104 @@
+//! Tests for biome ids and their compatibility with old save data.
+
+use std::path::{Path, PathBuf};
+
+use gaia_maker_core::planet::*;
+
+const LEGACY_BIOMES: &[(u64, &str)] = &[
+    (1, "rock"),
+    (2, "ice-sheet"),
+    (3, "desert"),
+    (21, "ocean"),
+    (22, "sea-ice"),
+    (41, "grassland"),
+    (42, "tundra"),
+    (61, "boreal-forest"),
+    (62, "temperate-forest"),
+    (63, "tropical-rainforest"),
+];
+
+fn assets_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("../assets")
+}
+
+fn load_params() -> Params {
+    gaia_maker_core::loader::load_params(&assets_dir()).unwrap()
+}
+
+#[test]
+fn legacy_number() {
+    let params = load_params();
+
+    for &(n, id) in LEGACY_BIOMES {
+        let biome = Biome::from_legacy_number(n).unwrap();
+        assert_eq!(biome.to_string(), id);
+        assert!(params.biomes.contains_key(&biome), "{id} is not registered");
+
+        let data = rmp_serde::to_vec(&n).unwrap();
+        assert_eq!(rmp_serde::from_slice::<Biome>(&data).unwrap(), biome);
+    }
+    assert_eq!(Biome::from_legacy_number(0), None);
+}
+
+#[test]
+fn unknown_legacy_number() {
+    let params = load_params();
+
+    let data = rmp_serde::to_vec(&99u64).unwrap();
+    let biome: Biome = rmp_serde::from_slice(&data).unwrap();
+    assert!(!params.biomes.contains_key(&biome));
+}
+
+#[test]
+fn string_round_trip() {
+    let params = load_params();
+
+    for biome in params.biome_list() {
+        assert_eq!(biome.to_string().parse::<Biome>().unwrap(), biome);
+
+        let data = rmp_serde::to_vec(&biome).unwrap();
+        assert_eq!(rmp_serde::from_slice::<Biome>(&data).unwrap(), biome);
+
+        let s = ron::to_string(&biome).unwrap();
+        assert_eq!(ron::from_str::<Biome>(&s).unwrap(), biome);
+    }
+}
+
+#[test]
+fn check_biome_ids() {
+    let mut params = load_params();
+    assert_eq!(params.check_biome_ids(), Ok(()));
+
+    let attr = params.animals.values_mut().next().unwrap();
+    attr.habitat = AnimalHabitat::Biomes(vec![Biome::new("no-such-biome")]);
+    assert!(params.check_biome_ids().is_err());
+
+    let mut params = load_params();
+    params.biomes.remove(&Biome::SEA_ICE);
+    assert!(params.check_biome_ids().is_err());
+}
+
+#[test]
+fn migrate_unknown_biome() {
+    let params = load_params();
+    let start_params = start_planet_to_start_params("continental", &params, Some(1));
+    let mut planet = Planet::new(&start_params, &params);
+
+    let unknown = Biome::new("no-such-biome");
+    let p_ocean = planet
+        .map
+        .iter_idx()
+        .find(|&p| planet.is_ocean_tile(p))
+        .unwrap();
+    let p_land = planet
+        .map
+        .iter_idx()
+        .find(|&p| !planet.is_ocean_tile(p))
+        .unwrap();
+    planet.map[p_ocean].biome = unknown;
+    planet.map[p_land].biome = unknown;
+
+    planet.migrate(&params);
+    assert_eq!(planet.map[p_ocean].biome, Biome::OCEAN);
+    assert_eq!(planet.map[p_land].biome, Biome::ROCK);
+}
@@ -0,0 +1,78 @@
+//! Tests for player actions recorded in journals.
+
+use std::path::{Path, PathBuf};
+
+use gaia_maker_core::planet::*;
+use tile_geom::Coords;
+
+const SEED: u64 = 1;
+
+fn assets_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("../assets")
+}
+
+#[test]
+fn reject_structure_not_buildable_by_player() {
+    let params = gaia_maker_core::loader::load_params(&assets_dir()).unwrap();
+    let start_params = start_planet_to_start_params("continental", &params, Some(SEED));
+    let mut planet = Planet::new(&start_params, &params);
+    let mut sim = Sim::new(&planet, &params);
+    planet.res.material = 1.0e+6;
+
+    let p = Coords(0, 0);
+    let action = PlayerAction::Build {
+        p,
+        kind: StructureKind::SETTLEMENT,
+    };
+    assert!(!planet.action_applicable(&action, &params));
+    assert!(!planet.apply_action(&action, &mut sim, &params));
+
+    // A journal entry of the action is skipped by replay
+    let mut journal = planet.journal.clone();
+    journal.entries.push(JournalEntry {
+        cycles: 0,
+        seed: 0,
+        action,
+        repeat: 0,
+    });
+    let (mut replay, mut planet) = Replay::new(&journal, &params).unwrap();
+    let mut sim = Sim::new(&planet, &params);
+    planet.res.material = 1.0e+6;
+    replay.apply_actions(&mut planet, &mut sim, &params);
+    assert!(replay.finished());
+    assert!(planet.map[p].structure.is_none());
+}
+
+#[test]
+fn coalesce_repeated_actions() {
+    let params = gaia_maker_core::loader::load_params(&assets_dir()).unwrap();
+    let start_params = start_planet_to_start_params("continental", &params, Some(SEED));
+    let mut planet = Planet::new(&start_params, &params);
+    let mut sim = Sim::new(&planet, &params);
+    planet.res.material = 1.0e+6;
+
+    let kind = SpaceBuildingKind::new("fusion-reactor");
+    let action = PlayerAction::BuildSpaceBuilding { kind };
+    for _ in 0..5 {
+        assert!(planet.apply_action(&action, &mut sim, &params));
+    }
+    assert_eq!(planet.journal.entries.len(), 1);
+    assert_eq!(planet.journal.entries[0].repeat, 4);
+
+    // Replay reproduces all repetitions and records them in the same way
+    let (mut replay, mut replayed) = Replay::new(&planet.journal, &params).unwrap();
+    let mut sim = Sim::new(&replayed, &params);
+    replayed.res.material = 1.0e+6;
+    replay.apply_actions(&mut replayed, &mut sim, &params);
+    assert!(replay.finished());
+    assert_eq!(
+        replayed.space_building(kind).n,
+        planet.space_building(kind).n
+    );
+    assert_eq!(replayed.journal.entries.len(), 1);
+    assert_eq!(
+        replayed.journal.entries[0].seed,
+        planet.journal.entries[0].seed
+    );
+    assert_eq!(replayed.journal.entries[0].repeat, 4);
+}
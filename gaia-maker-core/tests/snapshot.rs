@@ -0,0 +1,199 @@
+//! Golden-snapshot tests for shipped start planets.
+//!
+//! Each start planet is created with a fixed seed, advanced by `CYCLES` and compared with
+//! `tests/snapshots/<id>.ron`. Set `GAIA_MAKER_UPDATE_SNAPSHOTS=1` to regenerate snapshots.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use gaia_maker_core::planet::*;
+use serde::{Deserialize, Serialize};
+use strum::IntoEnumIterator;
+
+const SEED: u64 = 1;
+const CYCLES: u64 = 200;
+const REPORT_LIFESPAN: u64 = 10000;
+
+/// Relative tolerance for float values
+const REL_TOLERANCE: f32 = 0.01;
+/// Absolute tolerance for float values close to zero
+const ABS_TOLERANCE: f32 = 1.0e-6;
+/// Tolerance of the number of tiles for each biome, as a ratio to the total tiles
+const BIOME_TOLERANCE: f32 = 0.01;
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Snapshot {
+    cycles: u64,
+    average_air_temp: f32,
+    average_sea_temp: f32,
+    average_rainfall: f32,
+    sum_biomass: f32,
+    sum_buried_carbon: f32,
+    biomes: BTreeMap<String, u32>,
+    partial_pressure: BTreeMap<String, f32>,
+    civs: usize,
+}
+
+impl Snapshot {
+    fn new(planet: &Planet, params: &Params) -> Self {
+        let mut biomes: BTreeMap<String, u32> = params
+            .biome_list()
+            .into_iter()
+            .map(|biome| (biome.to_string(), 0))
+            .collect();
+        for tile in planet.map.iter() {
+            *biomes.get_mut(&tile.biome.to_string()).unwrap() += 1;
+        }
+
+        Self {
+            cycles: planet.cycles,
+            average_air_temp: planet.stat.average_air_temp,
+            average_sea_temp: planet.stat.average_sea_temp,
+            average_rainfall: planet.stat.average_rainfall,
+            sum_biomass: planet.stat.sum_biomass,
+            sum_buried_carbon: planet.stat.sum_buried_carbon,
+            biomes,
+            partial_pressure: GasKind::iter()
+                .map(|kind| (kind.as_ref().to_owned(), planet.atmo.partial_pressure(kind)))
+                .collect(),
+            civs: planet.civs.len(),
+        }
+    }
+
+    fn diff(&self, expected: &Snapshot, n_tiles: u32) -> Vec<String> {
+        let mut diff = Vec::new();
+
+        let mut check_f32 = |name: &str, value: f32, expected: f32| {
+            if (value - expected).abs() > (expected.abs() * REL_TOLERANCE).max(ABS_TOLERANCE) {
+                diff.push(format!("{name}: {value} (expected {expected})"));
+            }
+        };
+        check_f32(
+            "average_air_temp",
+            self.average_air_temp,
+            expected.average_air_temp,
+        );
+        check_f32(
+            "average_sea_temp",
+            self.average_sea_temp,
+            expected.average_sea_temp,
+        );
+        check_f32(
+            "average_rainfall",
+            self.average_rainfall,
+            expected.average_rainfall,
+        );
+        check_f32("sum_biomass", self.sum_biomass, expected.sum_biomass);
+        check_f32(
+            "sum_buried_carbon",
+            self.sum_buried_carbon,
+            expected.sum_buried_carbon,
+        );
+        for (kind, expected_value) in &expected.partial_pressure {
+            let value = self.partial_pressure.get(kind).copied().unwrap_or_default();
+            check_f32(&format!("partial_pressure.{kind}"), value, *expected_value);
+        }
+
+        if self.cycles != expected.cycles {
+            diff.push(format!(
+                "cycles: {} (expected {})",
+                self.cycles, expected.cycles
+            ));
+        }
+        let biome_tolerance = (n_tiles as f32 * BIOME_TOLERANCE).ceil() as u32;
+        for (biome, expected_n) in &expected.biomes {
+            let n = self.biomes.get(biome).copied().unwrap_or_default();
+            if n.abs_diff(*expected_n) > biome_tolerance {
+                diff.push(format!("biomes.{biome}: {n} (expected {expected_n})"));
+            }
+        }
+        if self.civs != expected.civs {
+            diff.push(format!("civs: {} (expected {})", self.civs, expected.civs));
+        }
+
+        diff
+    }
+}
+
+fn assets_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("../assets")
+}
+
+fn snapshot_path(id: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/snapshots")
+        .join(format!("{id}.ron"))
+}
+
+fn update_enabled() -> bool {
+    std::env::var("GAIA_MAKER_UPDATE_SNAPSHOTS").is_ok_and(|s| !s.is_empty() && s != "0")
+}
+
+fn check_snapshot(id: &str) {
+    let params = gaia_maker_core::loader::load_params(&assets_dir()).unwrap();
+    let mut start_params = start_planet_to_start_params(id, &params, Some(SEED));
+    start_params.basics.name = id.into();
+    let mut planet = Planet::new(&start_params, &params);
+    let mut sim = Sim::new(&planet, &params);
+    planet.update(&mut sim, &params);
+
+    for _ in 0..CYCLES {
+        planet.advance(&mut sim, &params);
+        planet.monitor(&params, REPORT_LIFESPAN);
+    }
+
+    let snapshot = Snapshot::new(&planet, &params);
+    let path = snapshot_path(id);
+
+    if update_enabled() {
+        let s = ron::ser::to_string_pretty(&snapshot, ron::ser::PrettyConfig::default()).unwrap();
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, s + "\n").unwrap();
+        eprintln!("snapshot written to {}", path.display());
+        return;
+    }
+
+    assert!(
+        path.exists(),
+        "snapshot for \"{}\" not found. Run with GAIA_MAKER_UPDATE_SNAPSHOTS=1 to create it.",
+        id
+    );
+    let expected: Snapshot = ron::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+    let diff = snapshot.diff(&expected, planet.n_tile());
+    assert!(
+        diff.is_empty(),
+        "snapshot mismatch for \"{}\". Run with GAIA_MAKER_UPDATE_SNAPSHOTS=1 to update.\n{}",
+        id,
+        diff.join("\n")
+    );
+}
+
+#[test]
+fn archipelago() {
+    check_snapshot("archipelago");
+}
+
+#[test]
+fn barren() {
+    check_snapshot("barren");
+}
+
+#[test]
+fn continental() {
+    check_snapshot("continental");
+}
+
+#[test]
+fn desert() {
+    check_snapshot("desert");
+}
+
+#[test]
+fn ice() {
+    check_snapshot("ice");
+}
+
+#[test]
+fn tutorial() {
+    check_snapshot("tutorial");
+}
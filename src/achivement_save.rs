@@ -77,6 +77,7 @@ fn check_periodic(
     mut sim: ResMut<Sim>,
     se_player: SoundEffectPlayer,
     time: Res<Time<Real>>,
+    mut last_checked_cycles: Local<u64>,
 ) {
     if let Some(timer) = &mut achivement_notification.timer {
         timer.tick(time.delta());
@@ -85,9 +86,13 @@ fn check_periodic(
         }
     }
 
-    if planet.cycles % CHECK_ACHIVEMENT_INTERVAL_CYCLES != 0 {
+    // Snapshots from the simulation worker may skip some cycles
+    if planet.cycles / CHECK_ACHIVEMENT_INTERVAL_CYCLES
+        == *last_checked_cycles / CHECK_ACHIVEMENT_INTERVAL_CYCLES
+    {
         return;
     }
+    *last_checked_cycles = planet.cycles;
 
     let mut new_achivements = FnvHashSet::default();
 
@@ -3,10 +3,11 @@ use geom::Coords;
 
 use crate::audio::SoundEffectPlayer;
 use crate::draw::UpdateDraw;
-use crate::planet::debug::PlanetDebug;
 use crate::planet::*;
-use crate::screen::{CauseEventKind, CursorMode};
+use crate::screen::CursorMode;
+use crate::sim_worker::{DebugCommand, SimWorker};
 use crate::ui::{Dialog, WindowsOpenState};
+use crate::undo::UndoHistory;
 use crate::{GameState, GameSystemSet};
 
 #[derive(Clone, Copy, Debug)]
@@ -33,19 +34,29 @@ fn cursor_action(
     mut er: EventReader<CursorAction>,
     mut update_draw: ResMut<UpdateDraw>,
     cursor_mode: Res<CursorMode>,
-    mut sim: ResMut<Sim>,
     params: Res<Params>,
-    mut planet: ResMut<Planet>,
+    planet: Res<Planet>,
     mut wos: ResMut<WindowsOpenState>,
+    mut history: ResMut<UndoHistory>,
+    mut worker: ResMut<SimWorker>,
+    replay: Option<Res<Replay>>,
     se_player: SoundEffectPlayer,
 ) {
+    if replay.is_some() {
+        er.clear();
+        return;
+    }
+
     for e in er.read() {
         let CursorAction { p, .. } = *e;
 
         match *cursor_mode {
             CursorMode::Normal => (),
             CursorMode::Demolition => {
-                if planet.demolition(p, &mut sim, &params) {
+                let action = PlayerAction::Demolition { p };
+                if planet.action_applicable(&action, &params) {
+                    history.record(&planet);
+                    worker.apply_action(action, &planet, &params);
                     se_player.play("demolish");
                     update_draw.update();
                 }
@@ -55,19 +66,13 @@ fn cursor_action(
                     && let Some(id) = planet.get_civilizable_animal(p, &params)
                 {
                     if planet.civs.contains_key(&id) {
-                        update_draw.update();
-                        let age = CivilizationAge::Stone;
-                        planet.place_settlement(
-                            p,
-                            Settlement {
-                                id,
-                                age,
-                                pop: params.sim.settlement_init_pop[age as usize],
-                                ..Default::default()
-                            },
-                        );
-                        planet.res.consume(Cost::GenePoint(params.event.civilize_cost));
-                        se_player.play("civilize");
+                        let action = PlayerAction::Civilize { p, id };
+                        if planet.action_applicable(&action, &params) {
+                            history.record(&planet);
+                            update_draw.update();
+                            worker.apply_action(action, &planet, &params);
+                            se_player.play("civilize");
+                        }
                     } else {
                         wos.dialogs.push(Dialog::Civilize { p, id });
                         se_player.play("select-item");
@@ -77,14 +82,19 @@ fn cursor_action(
             CursorMode::Build(kind) => {
                 if planet.buildable(params.structures[&kind].as_ref()).is_ok() {
                     update_draw.update();
-                    if planet.placeable(p) {
-                        planet.place(p, new_structure(kind), &mut sim, &params);
+                    let action = PlayerAction::Build { p, kind };
+                    if planet.action_applicable(&action, &params) {
+                        history.record(&planet);
+                        worker.apply_action(action, &planet, &params);
                         se_player.play("build");
                     }
                 }
             }
             CursorMode::TileEvent(kind) => {
-                if planet.cause_tile_event(p, kind, &mut sim, &params) {
+                let action = PlayerAction::CauseTileEvent { p, kind };
+                if planet.action_applicable(&action, &params) {
+                    history.record(&planet);
+                    worker.apply_action(action, &planet, &params);
                     update_draw.update();
                     let se = match kind {
                         TileEventKind::Fire => "fire",
@@ -101,23 +111,25 @@ fn cursor_action(
                 }
             }
             CursorMode::SpawnAnimal(animal_id) => {
-                if planet.animal_spawnable(p, animal_id, &params) {
+                let action = PlayerAction::SpawnAnimal { p, id: animal_id };
+                if planet.action_applicable(&action, &params) {
+                    history.record(&planet);
                     update_draw.update();
-                    planet.spawn_animal(p, animal_id, &params);
+                    worker.apply_action(action, &planet, &params);
                     se_player.play("spawn-animal");
                 }
             }
             CursorMode::EditBiome(biome) => {
                 update_draw.update();
-                planet.edit_biome(p, biome);
+                worker.debug(DebugCommand::EditBiome(p, biome));
             }
             CursorMode::ChangeHeight(value) => {
                 update_draw.update();
-                planet.change_height(p, value, &mut sim, &params);
+                worker.debug(DebugCommand::ChangeHeight(p, value));
             }
             CursorMode::PlaceSettlement(id, age) => {
                 update_draw.update();
-                planet.place_settlement(
+                worker.debug(DebugCommand::PlaceSettlement(
                     p,
                     Settlement {
                         id,
@@ -125,38 +137,16 @@ fn cursor_action(
                         pop: params.sim.settlement_init_pop[age as usize],
                         ..Default::default()
                     },
-                );
+                ));
             }
             CursorMode::CauseEvent(kind) => {
                 update_draw.update();
-                match kind {
-                    CauseEventKind::Decadence => {
-                        planet.cause_decadence(p, &mut sim, &params);
-                    }
-                    CauseEventKind::CivilWar => {
-                        planet.cause_civil_war(p, &mut sim, &params);
-                    }
-                    CauseEventKind::NuclearExplsion => {
-                        planet.cause_nuclear_explosion(p, &mut sim, &params);
-                    }
-                }
+                worker.debug(DebugCommand::CauseEvent(p, kind));
             }
         }
     }
 }
 
-fn new_structure(kind: StructureKind) -> Structure {
-    match kind {
-        StructureKind::OxygenGenerator => Structure::OxygenGenerator,
-        StructureKind::Rainmaker => Structure::Rainmaker,
-        StructureKind::FertilizationPlant => Structure::FertilizationPlant,
-        StructureKind::Heater => Structure::Heater,
-        StructureKind::CarbonCapturer => Structure::CarbonCapturer,
-        StructureKind::GiftTower => Structure::GiftTower,
-        _ => unreachable!(),
-    }
-}
-
 pub fn cursor_mode_lack_and_cost(
     planet: &Planet,
     params: &Params,
@@ -10,7 +10,7 @@ use compact_str::CompactString;
 use fnv::FnvHashMap;
 use serde::Deserialize;
 use serde_with::{DisplayFromStr, Same, serde_as};
-use strum::{AsRefStr, IntoEnumIterator};
+use strum::AsRefStr;
 
 use crate::GameState;
 use crate::audio::MusicListAsset;
@@ -34,6 +34,9 @@ impl Plugin for AssetsPlugin {
             .add_plugins(RonAssetPlugin::<StartPlanetAsset>::new(&[
                 "start_planet.ron",
             ]))
+            .add_plugins(RonAssetPlugin::<SpaceBuildingAsset>::new(&[
+                "space_building.ron",
+            ]))
             .add_plugins(RonAssetPlugin::<AnimalAsset>::new(&["animal.ron"]))
             .add_plugins(RonAssetPlugin::<MusicListAsset>::new(&["music.ron"]))
             .add_plugins(RonAssetPlugin::<CreditsAsset>::new(&["credits.ron"]))
@@ -43,8 +46,6 @@ impl Plugin for AssetsPlugin {
                     .load_collection::<crate::text_assets::TranslationTexts>()
                     .load_collection::<PlanetAssetCollection>()
                     .load_collection::<UiAssets>()
-                    .load_collection::<BiomeTextures>()
-                    .load_collection::<StructureTextures>()
                     .load_collection::<SoundEffectSources>()
                     .load_collection::<MusicLists>()
                     .load_collection::<Credits>(),
@@ -71,6 +72,8 @@ pub struct UiAssets {
     pub ui_imgs: HashMap<String, Handle<Image>>,
     #[asset(path = "start_planets", collection(mapped))]
     pub start_planet: HashMap<String, UntypedHandle>,
+    #[asset(path = "space_buildings", collection(mapped))]
+    pub space_building_imgs: HashMap<String, UntypedHandle>,
     #[asset(path = "animals", collection(mapped))]
     pub animal_imgs: HashMap<String, UntypedHandle>,
     #[asset(path = "ui/tile-colored.png")]
@@ -96,6 +99,10 @@ pub struct StructureAssetList(FnvHashMap<StructureKind, StructureAttrs>);
 #[serde(transparent)]
 pub struct StartPlanetAsset(StartPlanet);
 
+#[derive(Clone, Debug, Deserialize, Asset, TypePath)]
+#[serde(transparent)]
+pub struct SpaceBuildingAsset(SpaceBuildingAttrs);
+
 #[derive(Clone, Debug, Deserialize, Asset, TypePath)]
 #[serde(transparent)]
 pub struct AnimalAsset(AnimalAttr);
@@ -116,7 +123,9 @@ pub enum CreditSection {
 #[derive(Resource)]
 pub struct TextureHandles {
     pub biome_layouts: FnvHashMap<Biome, Handle<TextureAtlasLayout>>,
+    pub biome_images: FnvHashMap<Biome, Handle<Image>>,
     pub structure_layouts: FnvHashMap<StructureKind, Handle<TextureAtlasLayout>>,
+    pub structure_images: FnvHashMap<StructureKind, Handle<Image>>,
     pub animals: HashMap<AnimalId, LoadedTexture>,
     pub tile_animations: HashMap<CompactString, LoadedTexture>,
 }
@@ -135,32 +144,22 @@ pub struct PlanetAssetCollection {
     params: Handle<ParamsAsset>,
     #[asset(path = "biomes/list.biomes.ron")]
     biomes: Handle<BiomeAssetList>,
+    #[asset(path = "biomes", collection(mapped))]
+    biome_handles: HashMap<String, UntypedHandle>,
     #[asset(path = "structures/list.structures.ron")]
     structures: Handle<StructureAssetList>,
+    #[asset(path = "structures", collection(mapped))]
+    structure_handles: HashMap<String, UntypedHandle>,
     #[asset(path = "start_planets", collection(mapped))]
     start_planet_handles: HashMap<String, UntypedHandle>,
+    #[asset(path = "space_buildings", collection(mapped))]
+    space_building_handles: HashMap<String, UntypedHandle>,
     #[asset(path = "animals", collection(mapped))]
     animal_handles: HashMap<String, UntypedHandle>,
     #[asset(path = "tile_animations", collection(mapped, typed))]
     tile_animation_handles: HashMap<String, Handle<Image>>,
 }
 
-define_asset_list_from_enum! {
-    #[asset(dir_path = "biomes")]
-    #[asset(extension = "png")]
-    pub struct BiomeTextures {
-        pub textures: HashMap<Biome, Handle<Image>>,
-    }
-}
-
-define_asset_list_from_enum! {
-    #[asset(dir_path = "structures")]
-    #[asset(extension = "png")]
-    pub struct StructureTextures {
-        pub textures: HashMap<StructureKind, Handle<Image>>,
-    }
-}
-
 #[derive(Debug, Resource, AssetCollection)]
 pub struct SoundEffectSources {
     #[asset(path = "se", collection(mapped, typed))]
@@ -183,11 +182,19 @@ fn create_assets_list(
     mut command: Commands,
     images: Res<Assets<Image>>,
     planet_asset_collection: Res<PlanetAssetCollection>,
-    (params_asset, biome_asset_list, structure_asset_list, start_planet_assets, animal_assets): (
+    (
+        params_asset,
+        biome_asset_list,
+        structure_asset_list,
+        start_planet_assets,
+        space_building_assets,
+        animal_assets,
+    ): (
         Res<Assets<ParamsAsset>>,
         Res<Assets<BiomeAssetList>>,
         Res<Assets<StructureAssetList>>,
         Res<Assets<StartPlanetAsset>>,
+        Res<Assets<SpaceBuildingAsset>>,
         Res<Assets<AnimalAsset>>,
     ),
     mut texture_atlas_assets: ResMut<Assets<TextureAtlasLayout>>,
@@ -239,8 +246,10 @@ fn create_assets_list(
             );
         }
     }
-    let biomes = Biome::iter()
-        .map(|biome| {
+    let biomes = biome_asset_list
+        .0
+        .keys()
+        .map(|&biome| {
             let mut texture_atlas = TextureAtlasLayout::new_empty(UVec2::new(
                 (TILE_SIZE as u32 + 2) * 3,
                 (TILE_SIZE as u32 + 2) * 4,
@@ -253,14 +262,31 @@ fn create_assets_list(
             (biome, texture_atlas_assets.add(texture_atlas))
         })
         .collect();
+    let mut biome_images = FnvHashMap::default();
+    for (&biome, attrs) in &biome_asset_list.0 {
+        let Some(handle) = planet_asset_collection
+            .biome_handles
+            .get(&attrs.texture)
+            .and_then(|handle| handle.clone().try_typed::<Image>().ok())
+        else {
+            log::warn!(
+                "texture \"{}\" for biome {} not found",
+                attrs.texture,
+                biome
+            );
+            continue;
+        };
+        biome_images.insert(biome, handle);
+    }
 
     // Structures
     let structure_asset_list = structure_asset_list
         .get(&planet_asset_collection.structures)
         .unwrap();
-    let structures = StructureKind::iter()
-        .map(|structure| {
-            let attrs = &structure_asset_list.0[&structure];
+    let structures = structure_asset_list
+        .0
+        .iter()
+        .map(|(&structure, attrs)| {
             let texture_atlas = TextureAtlasLayout::from_grid(
                 UVec2::new(attrs.width, attrs.height),
                 attrs.columns as u32,
@@ -272,6 +298,22 @@ fn create_assets_list(
             (structure, texture_atlas_assets.add(texture_atlas))
         })
         .collect();
+    let mut structure_images = FnvHashMap::default();
+    for (&structure, attrs) in &structure_asset_list.0 {
+        let Some(handle) = planet_asset_collection
+            .structure_handles
+            .get(&attrs.texture)
+            .and_then(|handle| handle.clone().try_typed::<Image>().ok())
+        else {
+            log::warn!(
+                "texture \"{}\" for structure {} not found",
+                attrs.texture,
+                structure
+            );
+            continue;
+        };
+        structure_images.insert(structure, handle);
+    }
 
     let mut params = params_asset
         .get(&planet_asset_collection.params)
@@ -295,6 +337,27 @@ fn create_assets_list(
             o => o,
         });
 
+    // Space buildings
+    for (path, handle) in &planet_asset_collection.space_building_handles {
+        let Ok(handle) = handle.clone().try_typed::<SpaceBuildingAsset>() else {
+            continue;
+        };
+        let id = path
+            .strip_prefix("space_buildings/")
+            .and_then(|s| s.split_once('.'))
+            .expect("unexpected space building asset path")
+            .0;
+        let kind = match id.parse::<SpaceBuildingKind>() {
+            Ok(kind) => kind,
+            Err(e) => {
+                log::warn!("invalid space building id: {}", e);
+                continue;
+            }
+        };
+        let attrs = space_building_assets.get(&handle).cloned().unwrap().0;
+        params.space_buildings.insert(kind, attrs);
+    }
+
     // Animal
     for (path, handle) in &planet_asset_collection.animal_handles {
         let animal_id = path
@@ -401,10 +464,15 @@ fn create_assets_list(
         })
         .collect();
 
+    if let Err(e) = params.check_biome_ids() {
+        panic!("invalid biome id in assets: {}", e);
+    }
     command.insert_resource(params);
     command.insert_resource(TextureHandles {
         biome_layouts: biomes,
+        biome_images,
         structure_layouts: structures,
+        structure_images,
         animals,
         tile_animations,
     });
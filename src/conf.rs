@@ -71,6 +71,9 @@ pub struct Conf {
     pub bgm_volume: u8,
     pub slow_speed_sim_duration_ms: u64,
     pub medium_speed_sim_duration_ms: u64,
+    /// Undo history is kept for this number of cycles. 0 means until the next cycle
+    #[serde(default)]
+    pub undo_cycles: u64,
     pub window: Option<WindowConf>,
 }
 
@@ -152,7 +152,6 @@ fn spawn_map_textures(
     update_draw: Res<UpdateDraw>,
     ltm: Res<LayeredTexMap>,
     params: Res<Params>,
-    biome_textures: Res<BiomeTextures>,
     texture_handles: Res<TextureHandles>,
     in_screen_tile_range: Res<InScreenTileRange>,
     current_layer: Res<OverlayLayerKind>,
@@ -201,10 +200,13 @@ fn spawn_map_textures(
                     + PIECE_SIZE / 2.0;
 
                 let tile_asset = &params.biomes[tile_idx];
+                let Some(image) = texture_handles.biome_images.get(tile_idx) else {
+                    continue;
+                };
                 let id = commands
                     .spawn((
                         Sprite::from_atlas_image(
-                            biome_textures.get(*tile_idx),
+                            image.clone(),
                             TextureAtlas {
                                 index,
                                 layout: texture_handles.biome_layouts[tile_idx].clone(),
@@ -223,7 +225,6 @@ fn spawn_structure_textures(
     mut commands: Commands,
     update_draw: Res<UpdateDraw>,
     params: Res<Params>,
-    structure_textures: Res<StructureTextures>,
     texture_handles: Res<TextureHandles>,
     in_screen_tile_range: Res<InScreenTileRange>,
     planet: Res<Planet>,
@@ -249,6 +250,9 @@ fn spawn_structure_textures(
 
         let kind: StructureKind = structure.into();
         let attrs = &params.structures[&kind];
+        let Some(image) = texture_handles.structure_images.get(&kind) else {
+            continue;
+        };
 
         let (index, civ_icon) = if let Structure::Settlement(settlement) = structure {
             if !display_opts.cities {
@@ -262,7 +266,7 @@ fn spawn_structure_textures(
             } else {
                 None
             };
-            let aquatic_shift = if tile.biome.is_sea() { 6 } else { 0 };
+            let aquatic_shift = if tile.biome.is_sea(&params) { 6 } else { 0 };
             (settlement.age as usize + aquatic_shift, civ_icon)
         } else {
             if !display_opts.structures {
@@ -281,7 +285,7 @@ fn spawn_structure_textures(
         let id = commands
             .spawn((
                 Sprite::from_atlas_image(
-                    structure_textures.get(kind),
+                    image.clone(),
                     TextureAtlas {
                         index,
                         layout: texture_handles.structure_layouts[&kind].clone(),
@@ -293,7 +297,7 @@ fn spawn_structure_textures(
         tex_entities.push(id);
 
         if let Some(id) = civ_icon {
-            let t = &texture_handles.animals[&id];
+            let t = &texture_handles.animals[&planet.origin_species(id)];
             let x = x - (TILE_SIZE - t.width as f32) / 2.0;
             let y = y - (TILE_SIZE - t.height as f32) / 2.0;
             let id = commands
@@ -320,6 +324,7 @@ fn spawn_animal_textures(
     texture_handles: Res<TextureHandles>,
     in_screen_tile_range: Res<InScreenTileRange>,
     planet: Res<Planet>,
+    params: Res<Params>,
     (current_layer, display_opts): (Res<OverlayLayerKind>, Res<DisplayOpts>),
     counter: Res<AnimationCounter>,
     mut tex_entities: Local<Vec<Entity>>,
@@ -364,7 +369,7 @@ fn spawn_animal_textures(
             continue;
         };
 
-        let t = &texture_handles.animals[&animal.id];
+        let t = &texture_handles.animals[&planet.origin_species(animal.id)];
         let animated_texture = AnimatedTexture {
             speed: AnimatedTextureSpeed::Slow,
             n_frame: 2,
@@ -373,7 +378,11 @@ fn spawn_animal_textures(
         };
         let index = animated_texture.index(counter.slow, monochrome);
 
-        let alpha = if tile.biome == Biome::Ocean { 0.7 } else { 1.0 };
+        let alpha = if tile.biome.is_open_sea(&params) {
+            0.7
+        } else {
+            1.0
+        };
 
         let x = (p_screen.0 as f32 + 0.5) * TILE_SIZE;
         let y = (p_screen.1 as f32 + 0.5) * TILE_SIZE;
@@ -402,6 +411,7 @@ fn spawn_tile_animation_textures(
     texture_handles: Res<TextureHandles>,
     in_screen_tile_range: Res<InScreenTileRange>,
     planet: Res<Planet>,
+    params: Res<Params>,
     current_layer: Res<OverlayLayerKind>,
     counter: Res<AnimationCounter>,
     mut tex_entities: Local<Vec<Entity>>,
@@ -432,7 +442,7 @@ fn spawn_tile_animation_textures(
         else {
             continue;
         };
-        let animated_texture = tile_event_texture(tile_event, tile);
+        let animated_texture = tile_event_texture(tile_event, tile, &params);
         let index = animated_texture.index(counter.fast, monochrome);
         let Some(t) = texture_handles.tile_animations.get(tile_event.kind().as_ref()) else {
             continue;
@@ -588,7 +598,7 @@ fn tile_event_order_key(tile_event: &TileEvent) -> u32 {
     }
 }
 
-fn tile_event_texture(tile_event: &TileEvent, tile: &Tile) -> AnimatedTexture {
+fn tile_event_texture(tile_event: &TileEvent, tile: &Tile, params: &Params) -> AnimatedTexture {
     match tile_event {
         TileEvent::Vehicle {
             kind,
@@ -616,7 +626,7 @@ fn tile_event_texture(tile_event: &TileEvent, tile: &Tile) -> AnimatedTexture {
         }
         TileEvent::Troop { age, .. } => {
             let mut start = 0;
-            if tile.biome.is_sea() {
+            if tile.biome.is_sea(params) {
                 start += 2;
             }
             if *age >= CivilizationAge::Industrial {
@@ -0,0 +1,236 @@
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use anyhow::{Result, bail};
+use gaia_maker_core::loader::load_params;
+use serde::Serialize;
+use strum::IntoEnumIterator;
+
+use crate::planet::*;
+use crate::saveload::SaveFileMetadata;
+
+/// Run a planet simulation for the given cycles without opening a window
+#[derive(Clone, clap::Args, Debug)]
+pub struct HeadlessArgs {
+    /// Assets directory path
+    #[arg(long, default_value = "assets")]
+    assets_dir: PathBuf,
+    /// Start planet id used to create a new planet
+    #[arg(long, required_unless_present = "load", conflicts_with = "load")]
+    start_planet: Option<String>,
+    /// Save file (.planet) to load
+    #[arg(long)]
+    load: Option<PathBuf>,
+    /// Name of the new planet
+    #[arg(long)]
+    name: Option<String>,
+    /// Random seed for the new planet
+    #[arg(long, conflicts_with = "load")]
+    seed: Option<u64>,
+    /// Re-run the loaded planet from the start by its journal, up to the cycles of the save file
+    #[arg(long, requires = "load")]
+    replay: bool,
+    /// The number of cycles to advance. Ignored for replay
+    #[arg(long, default_value_t = 1000)]
+    cycles: u64,
+    /// Save file path to write the result
+    #[arg(long)]
+    output: Option<PathBuf>,
+    /// Print stat summary at each this number of cycles. 0 prints only the result
+    #[arg(long, default_value_t = 0)]
+    stat_interval: u64,
+    /// Print stat summaries as JSON lines
+    #[arg(long)]
+    json: bool,
+    /// Print time taken by each simulation stage at the end
+    #[arg(long)]
+    profile: bool,
+    /// Lifespan of reports in cycles
+    #[arg(long, default_value_t = 10000)]
+    report_lifespan: u64,
+}
+
+pub fn run(args: HeadlessArgs) -> Result<()> {
+    let params = load_params(&args.assets_dir)?;
+
+    let (mut planet, metadata) = if let Some(path) = &args.load {
+        let (mut planet, metadata) = crate::saveload::load_from_file(path)?;
+        planet.migrate(&params);
+        (planet, metadata)
+    } else {
+        let id = args.start_planet.as_deref().unwrap();
+        if !params
+            .start_planets
+            .iter()
+            .any(|start_planet| start_planet.id == id)
+        {
+            bail!("unknown start planet \"{}\"", id);
+        }
+        let mut start_params = start_planet_to_start_params(id, &params, args.seed);
+        start_params.basics.name = args.name.clone().unwrap_or_else(|| id.to_owned());
+        (
+            Planet::new(&start_params, &params),
+            SaveFileMetadata::default(),
+        )
+    };
+
+    let (mut replay, cycles) = if args.replay {
+        let Some((replay, replay_planet)) = Replay::new(&planet.journal, &params) else {
+            bail!("the save file has no journal to replay");
+        };
+        let cycles = planet.cycles;
+        planet = replay_planet;
+        (Some(replay), cycles)
+    } else {
+        (None, args.cycles)
+    };
+
+    let mut sim = Sim::new(&planet, &params);
+    planet.update(&mut sim, &params);
+
+    for _ in 0..cycles {
+        if let Some(replay) = &mut replay {
+            replay.apply_actions(&mut planet, &mut sim, &params);
+        }
+        planet.advance(&mut sim, &params);
+        planet.monitor(&params, args.report_lifespan);
+
+        if args.stat_interval > 0 && planet.cycles % args.stat_interval == 0 {
+            print_stat(&planet, args.json)?;
+        }
+    }
+    if args.stat_interval == 0 || planet.cycles % args.stat_interval != 0 {
+        print_stat(&planet, args.json)?;
+    }
+
+    if args.profile {
+        print_profile(&sim.profiler, args.json)?;
+    }
+
+    if let Some(output) = &args.output {
+        crate::saveload::save_to_file(&planet, metadata, output)?;
+        log::info!("saved to {}", output.display());
+    }
+
+    Ok(())
+}
+
+fn print_profile(profiler: &Profiler, json: bool) -> Result<()> {
+    if json {
+        println!(
+            "{}",
+            serde_json::json!({ "n_cycles": profiler.n_cycles, "stages": profiler.stages() })
+        );
+    } else {
+        println!(
+            "{:<16}{:>12}{:>12}{:>12}",
+            "stage", "last[ms]", "avg[ms]", "max[ms]"
+        );
+        for stage in profiler.stages() {
+            println!(
+                "{:<16}{:>12.3}{:>12.3}{:>12.3}",
+                stage.name, stage.last, stage.average, stage.max
+            );
+        }
+        println!("{:<16}{:>24.3}", "total", profiler.total_average());
+    }
+    Ok(())
+}
+
+#[derive(Clone, Debug, Serialize)]
+struct StatSummary {
+    cycles: u64,
+    seed: u64,
+    /// Average air temperature [°C]
+    average_air_temp: f32,
+    /// Average sea temperature [°C]
+    average_sea_temp: f32,
+    /// Average rainfall [mm]
+    average_rainfall: f32,
+    /// Biomass [Mt]
+    biomass: f32,
+    /// Buried carbon [Mt]
+    buried_carbon: f32,
+    /// Atmospheric pressure [atm]
+    atm: f32,
+    /// Partial pressure of each gas [atm]
+    partial_pressure: BTreeMap<GasKind, f32>,
+    material: f32,
+    gene_point: f32,
+    animals: BTreeMap<AnimalId, f32>,
+    civ_pop: BTreeMap<AnimalId, f32>,
+}
+
+impl StatSummary {
+    fn new(planet: &Planet) -> Self {
+        Self {
+            cycles: planet.cycles,
+            seed: planet.seed,
+            average_air_temp: planet.stat.average_air_temp - KELVIN_CELSIUS,
+            average_sea_temp: planet.stat.average_sea_temp - KELVIN_CELSIUS,
+            average_rainfall: planet.stat.average_rainfall,
+            biomass: planet.stat.sum_biomass,
+            buried_carbon: planet.stat.sum_buried_carbon,
+            atm: planet.atmo.atm(),
+            partial_pressure: GasKind::iter()
+                .map(|kind| (kind, planet.atmo.partial_pressure(kind)))
+                .collect(),
+            material: planet.res.material,
+            gene_point: planet.res.gene_point,
+            animals: planet
+                .stat
+                .animals
+                .iter()
+                .filter(|(_, n)| **n > 0.0)
+                .map(|(id, n)| (*id, *n))
+                .collect(),
+            civ_pop: planet
+                .civs
+                .iter()
+                .map(|(id, civ)| (*id, civ.total_pop))
+                .collect(),
+        }
+    }
+}
+
+impl std::fmt::Display for StatSummary {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "cycles={} seed={} temp={:.1}°C sea_temp={:.1}°C rainfall={:.0}mm biomass={:.1}Gt buried_carbon={:.1}Gt atm={:.3}",
+            self.cycles,
+            self.seed,
+            self.average_air_temp,
+            self.average_sea_temp,
+            self.average_rainfall,
+            self.biomass * 1e-3,
+            self.buried_carbon * 1e-3,
+            self.atm,
+        )?;
+        for (kind, value) in &self.partial_pressure {
+            write!(f, " {}={:.2e}", kind.as_ref(), value)?;
+        }
+        write!(
+            f,
+            " material={:.0} gene_point={:.0} animals={} civs={}",
+            self.material,
+            self.gene_point,
+            self.animals.len(),
+            self.civ_pop.len()
+        )?;
+        for (id, pop) in &self.civ_pop {
+            write!(f, " {id}_pop={pop:.0}")?;
+        }
+        Ok(())
+    }
+}
+
+fn print_stat(planet: &Planet, json: bool) -> Result<()> {
+    let summary = StatSummary::new(planet);
+    if json {
+        println!("{}", serde_json::to_string(&summary)?);
+    } else {
+        println!("{summary}");
+    }
+    Ok(())
+}
@@ -2,8 +2,6 @@
 
 extern crate tile_geom as geom;
 
-#[macro_use]
-mod tools;
 #[macro_use]
 mod text_assets;
 
@@ -14,17 +12,20 @@ mod audio;
 mod conf;
 mod draw;
 mod gz;
+#[cfg(not(target_arch = "wasm32"))]
+mod headless;
 mod image_assets;
 mod manage_planet;
 mod overlay;
-mod planet;
 mod platform;
 mod saveload;
 mod screen;
+mod sim_worker;
 mod text;
 mod title_screen;
 mod tutorial;
 mod ui;
+mod undo;
 
 use std::path::PathBuf;
 
@@ -34,6 +35,7 @@ use bevy::{
     winit::WinitSettings,
 };
 use clap::Parser;
+use gaia_maker_core::planet;
 
 const APP_NAME: &str = concat!("Gaia Maker v", env!("CARGO_PKG_VERSION"));
 
@@ -48,14 +50,33 @@ struct Args {
     log_file: Option<PathBuf>,
     #[arg(long)]
     launcher_port: Option<u16>,
+    #[cfg(not(target_arch = "wasm32"))]
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Clone, clap::Subcommand, Debug)]
+enum Command {
+    /// Run simulation without opening a window
+    Headless(crate::headless::HeadlessArgs),
 }
 
 fn main() {
     let args = Args::parse();
     crate::platform::init_rayon(args.num_threads as usize);
+    crate::planet::set_default_civ_name_fn(|id| t!("civ", id));
     if let Some(log_file) = args.log_file {
         crate::platform::init_log_file(log_file);
     }
+    #[cfg(not(target_arch = "wasm32"))]
+    if let Some(Command::Headless(headless_args)) = args.command {
+        if let Err(e) = crate::headless::run(headless_args) {
+            eprintln!("error: {e:?}");
+            std::process::exit(1);
+        }
+        return;
+    }
     if let Some(port) = args.launcher_port {
         crate::platform::client::run_client(port);
     }
@@ -106,6 +127,7 @@ fn main() {
         .add_plugins(title_screen::TitleScreenPlugin)
         .add_plugins(draw::DrawPlugin)
         .add_plugins(action::ActionPlugin)
+        .add_plugins(undo::UndoPlugin)
         .add_plugins(manage_planet::ManagePlanetPlugin)
         .add_plugins(achivement_save::AchivementPlugin)
         .insert_resource(WinitSettings::game())
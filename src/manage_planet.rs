@@ -1,5 +1,3 @@
-use std::time::Duration;
-
 use anyhow::Context;
 use bevy::prelude::*;
 
@@ -7,6 +5,7 @@ use crate::conf::Conf;
 use crate::draw::UpdateDraw;
 use crate::saveload::SavedTime;
 use crate::screen::{Centering, HoverTile};
+use crate::sim_worker::{FastForwardState, SimWorker, WorkerTiming};
 use crate::tutorial::{TUTORIAL_PLANET, TutorialState};
 use crate::ui::{UiWindowsSystemSet, WindowsOpenState};
 use crate::{GameSpeed, GameState, GameSystemSet, planet::*};
@@ -42,6 +41,8 @@ pub enum ManagePlanet {
         auto: bool,
         n: u32,
     },
+    /// Re-run the current planet from the start by its journal
+    Replay,
 }
 
 #[derive(Clone, Default, Debug, Event)]
@@ -56,9 +57,6 @@ pub struct GlobalDataChanged {
 pub struct ManagePlanetSystemSet;
 
 impl Resource for SaveState {}
-impl Resource for Planet {}
-impl Resource for Params {}
-impl Resource for Sim {}
 
 impl Plugin for ManagePlanetPlugin {
     fn build(&self, app: &mut App) {
@@ -76,13 +74,22 @@ impl Plugin for ManagePlanetPlugin {
                 OnEnter(GameState::Running),
                 start_sim.in_set(GameSystemSet::StartSim),
             )
-            .add_systems(FixedUpdate, update.run_if(in_state(GameState::Running)))
+            .init_resource::<SimWorker>()
+            .init_resource::<FastForwardState>()
             .add_systems(
                 Update,
                 manage_planet
                     .before(GameSystemSet::Draw)
                     .in_set(ManagePlanetSystemSet),
             )
+            .add_systems(
+                Update,
+                update
+                    .run_if(in_state(GameState::Running))
+                    .after(ManagePlanetSystemSet)
+                    .before(GameSystemSet::Draw),
+            )
+            .add_systems(OnExit(GameState::Running), pause_worker)
             .add_systems(Update, save_global_data_on_changed)
             .add_systems(
                 Update,
@@ -158,90 +165,75 @@ fn start_sim(mut update_draw: ResMut<UpdateDraw>) {
 }
 
 fn update(
+    mut command: Commands,
     (mut planet, mut sim): (ResMut<Planet>, ResMut<Sim>),
+    replay: Option<Res<Replay>>,
+    mut worker: ResMut<SimWorker>,
     mut update_draw: ResMut<UpdateDraw>,
     mut ew_manage_planet: EventWriter<ManagePlanet>,
     params: Res<Params>,
     mut er_switch_planet: EventReader<SwitchPlanet>,
     mut speed: ResMut<GameSpeed>,
+    mut fast_forward: ResMut<FastForwardState>,
     hover_tile: Query<&HoverTile>,
-    time: Res<Time<Real>>,
     wos: Res<WindowsOpenState>,
     conf: Res<Conf>,
-    (mut last_advance_planet, mut last_update_draw, mut last_frame, mut delay_counter): (
-        Local<Duration>,
-        Local<Duration>,
-        Local<Duration>,
-        Local<u8>,
-    ),
+    #[cfg(target_arch = "wasm32")] time: Res<Time<Real>>,
 ) {
-    if wos.save || wos.load || !wos.dialogs.is_empty() {
-        return;
-    }
-
     if er_switch_planet.read().last().is_some() {
         *speed = GameSpeed::Paused;
-        planet.update(&mut sim, &params);
+        worker.start(&planet, replay.as_deref().cloned(), &params);
+        *fast_forward = FastForwardState::default();
+    } else if params.is_changed() {
+        worker.set_params(&params);
     }
 
-    let now = time.elapsed();
-
-    let advance_planet = match *speed {
-        GameSpeed::Paused => false,
-        GameSpeed::Slow => {
-            now - *last_advance_planet > Duration::from_millis(conf.slow_speed_sim_duration_ms)
-        }
-        GameSpeed::Medium => {
-            now - *last_advance_planet > Duration::from_millis(conf.medium_speed_sim_duration_ms)
-        }
-        GameSpeed::Fast => {
-            let diff = now - *last_frame;
-            if diff > Duration::from_millis(1000 / 50) {
-                let n = diff.subsec_millis() / (1000 / 60);
-                *delay_counter = delay_counter.saturating_add(n as u8);
-            } else {
-                *delay_counter = delay_counter.saturating_sub(1);
-            }
-            if *delay_counter < 100 {
-                true
-            } else {
-                now - *last_advance_planet
-                    > Duration::from_millis(conf.medium_speed_sim_duration_ms / 2)
-            }
-        }
-    };
-    let advance_planet = if advance_planet {
-        now - *last_frame < Duration::from_millis(1000 / 30)
+    let worker_speed = if wos.save || wos.load || !wos.dialogs.is_empty() {
+        GameSpeed::Paused
     } else {
-        advance_planet
+        *speed
     };
+    worker.set_speed(worker_speed, WorkerTiming::new(&conf));
+    crate::planet::debug::set_pos_for_log(
+        hover_tile.get_single().ok().and_then(|hover_tile| hover_tile.0),
+    );
 
-    if advance_planet {
-        crate::planet::debug::clear_logs(
-            hover_tile.get_single().ok().and_then(|hover_tile| hover_tile.0),
-        );
-        planet.advance(&mut sim, &params);
-        planet.monitor(&params, conf.report_lifespan);
-        *last_advance_planet = now;
-
-        if conf.autosave_enabled && planet.cycles % conf.autosave_cycle_duration == 0 {
-            ew_manage_planet.send(ManagePlanet::Save {
-                auto: true,
-                _new_name: None,
-            });
-        }
-    }
+    #[cfg(target_arch = "wasm32")]
+    worker.poll(time.elapsed());
 
-    let refresh_frame = match conf.screen_refresh_rate {
-        crate::conf::HighLow3::Low => 7,
-        crate::conf::HighLow3::Medium => 15,
-        crate::conf::HighLow3::High => 30,
+    let Some(snapshot) = worker.receive() else {
+        return;
     };
-    if advance_planet && now - *last_update_draw > Duration::from_millis(1000 / refresh_frame) {
-        *last_update_draw = now;
-        update_draw.update();
+    let prev_cycles = planet.cycles;
+    *planet = snapshot.planet;
+    snapshot.sim.apply(&mut sim);
+    update_draw.update();
+
+    if replay.is_some() && !snapshot.replaying {
+        command.remove_resource::<Replay>();
     }
-    *last_frame = now;
+
+    if fast_forward.running.is_some() && !snapshot.fast_forwarding {
+        fast_forward.running = None;
+        fast_forward.last_stop = snapshot.fast_forward_stop;
+        *speed = GameSpeed::Paused;
+    }
+
+    if conf.autosave_enabled
+        && replay.is_none()
+        && planet.cycles > prev_cycles
+        && planet.cycles / conf.autosave_cycle_duration
+            != prev_cycles / conf.autosave_cycle_duration
+    {
+        ew_manage_planet.send(ManagePlanet::Save {
+            auto: true,
+            _new_name: None,
+        });
+    }
+}
+
+fn pause_worker(mut worker: ResMut<SimWorker>, conf: Res<Conf>) {
+    worker.set_speed(GameSpeed::Paused, WorkerTiming::new(&conf));
 }
 
 #[derive(Clone, Debug, Event)]
@@ -275,7 +267,11 @@ fn manage_planet(
     };
     let new_planet = match e {
         ManagePlanet::New(start_params) => {
-            let mut planet = Planet::new(start_params, &params);
+            let mut start_params = start_params.clone();
+            if start_params.basics.origin == TUTORIAL_PLANET {
+                start_params.material_at_start = Some(8.0e+5); // Additional material for tutorial
+            }
+            let planet = Planet::new(&start_params, &params);
             let sub_dir_name = sanitize_filename::sanitize_with_options(
                 &start_params.basics.name,
                 sanitize_filename::Options {
@@ -292,7 +288,6 @@ fn manage_planet(
 
             if planet.basics.origin == TUTORIAL_PLANET {
                 save_state.save_file_metadata.tutorial_state = Some(TutorialState::default());
-                planet.res.material = 8.0e+5; // Additional material for tutorial
             }
 
             if let Err(e) = crate::saveload::save_to(&planet, &mut save_state, true) {
@@ -326,7 +321,8 @@ fn manage_planet(
             let prev_save_sub_dir = save_state.current_save_sub_dir.clone();
             save_state.change_current(sub_dir_name, false);
             match crate::saveload::load_from(&save_state, *auto, *n) {
-                Ok((planet, metadata)) => {
+                Ok((mut planet, metadata)) => {
+                    planet.migrate(&params);
                     save_state.save_file_metadata = metadata;
                     Some(planet)
                 }
@@ -377,9 +373,24 @@ fn manage_planet(
             }
             None
         }
+        ManagePlanet::Replay => {
+            let journal = &planet.as_ref().unwrap().journal;
+            if let Some((replay, new_planet)) = Replay::new(journal, &params) {
+                log::info!("start replay of {} actions", journal.entries.len());
+                command.insert_resource(replay);
+                Some(new_planet)
+            } else {
+                log::warn!("this planet has no journal to replay");
+                None
+            }
+        }
     };
 
     if let Some(new_planet) = new_planet {
+        if !matches!(e, ManagePlanet::Replay) {
+            command.remove_resource::<Replay>();
+        }
+
         ew_centering.send(Centering::new(Vec2::new(
             new_planet.map.size().0 as f32 * TILE_SIZE / 2.0,
             new_planet.map.size().1 as f32 * TILE_SIZE / 2.0,
@@ -2,7 +2,7 @@ use bevy::prelude::*;
 use geom::Coords;
 use strum::{AsRefStr, EnumIter};
 
-use crate::planet::{Biome, Params, Planet};
+use crate::planet::{Params, Planet};
 
 #[derive(Clone, Copy, Debug)]
 pub struct OverlayPlugin;
@@ -24,6 +24,8 @@ pub enum OverlayLayerKind {
     Fertility,
     Biomass,
     BuriedCarbon,
+    SurfaceUv,
+    CloudCover,
 }
 
 pub const N_POINTS: usize = 64;
@@ -57,7 +59,7 @@ impl ColorMaterials {
                 &self.white_yellow_red[i]
             }
             OverlayLayerKind::Rainfall => {
-                if planet.map[p].biome == Biome::Ocean {
+                if planet.map[p].biome.is_open_sea(params) {
                     &self.blue_dark_blue[32]
                 } else {
                     let rainfall = planet.map[p].rainfall;
@@ -102,6 +104,16 @@ impl ColorMaterials {
                 let i = (y * (N_POINTS as f32)).clamp(0.0, N_POINTS as f32 - 1.0) as usize;
                 &self.white_yellow_red[i]
             }
+            OverlayLayerKind::SurfaceUv => {
+                let i = (planet.tile_uv(p) / 10.0 * N_POINTS as f32)
+                    .clamp(0.0, N_POINTS as f32 - 1.0) as usize;
+                &self.white_yellow_red[i]
+            }
+            OverlayLayerKind::CloudCover => {
+                let i = (planet.map[p].cloud / 100.0 * N_POINTS as f32)
+                    .clamp(0.0, N_POINTS as f32 - 1.0) as usize;
+                &self.white_yellow_red[i]
+            }
         }
     }
 
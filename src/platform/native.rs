@@ -1,4 +1,8 @@
-use std::{io::Write, path::PathBuf, sync::Arc};
+use std::{
+    io::Write,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
 
 use anyhow::{Context, Result, anyhow};
 
@@ -46,10 +50,23 @@ pub fn write_savefile(dir_name: &str, file_name: &str, data: &[u8]) -> Result<()
         crate::platform::data_dir().ok_or_else(|| anyhow!("cannot get data directory path"))?;
     let save_dir_path = data_dir.join("saves").join(dir_name);
     std::fs::create_dir_all(&save_dir_path)?;
-    let file_path = save_dir_path.join(file_name);
+    write_compressed_file(&save_dir_path.join(file_name), data)
+}
+
+pub fn read_savefile(dir_name: &str, file_name: &str) -> Result<impl std::io::Read> {
+    let data_dir =
+        crate::platform::data_dir().ok_or_else(|| anyhow!("cannot get data directory path"))?;
+    let save_dir_path = data_dir.join("saves").join(dir_name);
+    read_compressed_file(&save_dir_path.join(file_name))
+}
 
+/// Write data to the given path with compression
+pub fn write_compressed_file(file_path: &Path, data: &[u8]) -> Result<()> {
     let mut w = zstd::stream::write::Encoder::new(
-        std::io::BufWriter::new(std::fs::File::create(file_path)?),
+        std::io::BufWriter::new(
+            std::fs::File::create(file_path)
+                .with_context(|| format!("creating \"{}\"", file_path.display()))?,
+        ),
         3,
     )?;
     w.write_all(data)?;
@@ -57,13 +74,9 @@ pub fn write_savefile(dir_name: &str, file_name: &str, data: &[u8]) -> Result<()
     Ok(())
 }
 
-pub fn read_savefile(dir_name: &str, file_name: &str) -> Result<impl std::io::Read> {
-    let data_dir =
-        crate::platform::data_dir().ok_or_else(|| anyhow!("cannot get data directory path"))?;
-    let save_dir_path = data_dir.join("saves").join(dir_name);
-    let file_path = save_dir_path.join(file_name);
-
-    let file = std::fs::File::open(&file_path)
+/// Read compressed data from the given path
+pub fn read_compressed_file(file_path: &Path) -> Result<impl std::io::Read + use<>> {
+    let file = std::fs::File::open(file_path)
         .with_context(|| format!("reading \"{}\"", file_path.display()))?;
 
     Ok(zstd::stream::read::Decoder::new(std::io::BufReader::new(
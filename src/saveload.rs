@@ -1,21 +1,14 @@
 use std::collections::{BTreeSet, VecDeque};
-use std::io::Read;
 
 use anyhow::Result;
-use byteorder::ReadBytesExt;
-use bytes::BufMut;
 use regex::Regex;
 use serde::{Deserialize, Serialize};
 
 use crate::{conf::Conf, planet::Planet, tutorial::TutorialState};
 
-pub const SAVE_FILE_EXTENSION: &str = "planet";
+pub use gaia_maker_core::savefile::{SAVE_FILE_EXTENSION, SavedTime};
 
-const GAME_VERSION: &str = env!("CARGO_PKG_VERSION");
-
-#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Debug, Serialize, Deserialize)]
-#[serde(transparent)]
-pub struct SavedTime(String);
+pub type SaveFile = gaia_maker_core::savefile::SaveFile<SaveFileMetadata>;
 
 #[derive(Default, Debug)]
 pub struct SaveState {
@@ -55,14 +48,7 @@ impl SaveState {
 }
 
 pub fn save_to(planet: &Planet, save_state: &mut SaveState, auto: bool) -> Result<(String, u32)> {
-    let planet_data = rmp_serde::to_vec(planet)?;
-
-    let bytes = SaveFile::new(
-        planet_data,
-        &planet.basics.name,
-        save_state.save_file_metadata.clone(),
-    )
-    .to_bytes();
+    let bytes = SaveFile::new(planet, save_state.save_file_metadata.clone())?.to_bytes();
 
     let n = if auto {
         save_state.auto_save_files.last().copied().unwrap_or_default() + 1
@@ -92,18 +78,36 @@ pub fn load_from(save_state: &SaveState, auto: bool, n: u32) -> Result<(Planet,
         "load save from {} version={} time=\"{}\"",
         file_name,
         data.version,
-        data.time.0
+        data.time
     );
-    let planet = rmp_serde::from_slice(&data.planet_data)?;
+    let planet = data.planet()?;
     Ok((planet, data.metadata))
 }
 
-pub struct SaveFile {
-    version: String,
-    time: SavedTime,
-    name: String,
+/// Save the planet to the given file path directly, not under the save directory.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn save_to_file(
+    planet: &Planet,
     metadata: SaveFileMetadata,
-    planet_data: Vec<u8>,
+    path: &std::path::Path,
+) -> Result<()> {
+    let bytes = SaveFile::new(planet, metadata)?.to_bytes();
+    crate::platform::write_compressed_file(path, &bytes)
+}
+
+/// Load a planet from the given file path directly, not under the save directory.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn load_from_file(path: &std::path::Path) -> Result<(Planet, SaveFileMetadata)> {
+    let reader = crate::platform::read_compressed_file(path)?;
+    let data = SaveFile::from_reader(reader, false)?;
+    log::info!(
+        "load save from {} version={} time=\"{}\"",
+        path.display(),
+        data.version,
+        data.time
+    );
+    let planet = data.planet()?;
+    Ok((planet, data.metadata))
 }
 
 #[derive(Clone, Default, Debug, Serialize, Deserialize)]
@@ -114,80 +118,6 @@ pub struct SaveFileMetadata {
     pub tutorial_state: Option<TutorialState>,
 }
 
-impl SaveFile {
-    fn new(planet_data: Vec<u8>, name: &str, metadata: SaveFileMetadata) -> Self {
-        Self {
-            version: GAME_VERSION.into(),
-            time: SavedTime::now(),
-            name: name.into(),
-            metadata,
-            planet_data,
-        }
-    }
-
-    fn to_bytes(&self) -> Vec<u8> {
-        let metadata = rmp_serde::to_vec_named(&self.metadata).unwrap();
-        let mut buf = Vec::new();
-
-        buf.put_u8(self.version.len().try_into().unwrap());
-        buf.put(self.version.as_bytes());
-        buf.put_u8(self.time.0.len().try_into().unwrap());
-        buf.put(self.time.0.as_bytes());
-        buf.put_u8(self.name.len().try_into().unwrap());
-        buf.put(self.name.as_bytes());
-        buf.put_u16(metadata.len().try_into().unwrap());
-        buf.put(&metadata[..]);
-        buf.put(&self.planet_data[..]);
-
-        buf
-    }
-
-    fn from_reader<R: Read>(mut reader: R, header_only: bool) -> Result<Self> {
-        let len = reader.read_u8()?;
-        let mut version = vec![0; len as usize];
-        reader.read_exact(&mut version)?;
-        let version = String::from_utf8(version)?;
-
-        let len = reader.read_u8()?;
-        let mut time = vec![0; len as usize];
-        reader.read_exact(&mut time)?;
-        let time = SavedTime(String::from_utf8(time)?);
-
-        let len = reader.read_u8()?;
-        let mut name = vec![0; len as usize];
-        reader.read_exact(&mut name)?;
-        let name = String::from_utf8(name)?;
-
-        let len = reader.read_u16::<byteorder::BigEndian>()?;
-        let mut metadata = vec![0; len as usize];
-        reader.read_exact(&mut metadata)?;
-
-        let planet_data = if header_only {
-            Vec::new()
-        } else {
-            let mut planet_data = Vec::new();
-            reader.read_to_end(&mut planet_data)?;
-            planet_data
-        };
-
-        let metadata = match rmp_serde::from_slice(&metadata) {
-            Ok(metadata) => metadata,
-            Err(e) => {
-                log::warn!("invalid metadata: {}", e);
-                SaveFileMetadata::default()
-            }
-        };
-
-        Ok(Self {
-            version,
-            time,
-            name,
-            metadata,
-            planet_data,
-        })
-    }
-}
-
 #[derive(Clone, Debug)]
 pub struct SaveSubDirItem {
     pub time: SavedTime,
@@ -313,29 +243,3 @@ pub fn save_file_name(auto: bool, n: u32) -> String {
         format!("{n:06}.{SAVE_FILE_EXTENSION}")
     }
 }
-
-impl SavedTime {
-    pub fn now() -> Self {
-        let time = chrono::Local::now().to_string();
-        Self(time.split_once('.').unwrap().0.into()) // Get "YYYY-MM-DD hh:mm:ss"
-    }
-}
-
-impl std::fmt::Display for SavedTime {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.0)
-    }
-}
-
-impl From<std::time::SystemTime> for SavedTime {
-    fn from(value: std::time::SystemTime) -> Self {
-        let time: chrono::DateTime<chrono::Local> = value.into();
-        Self::from(time)
-    }
-}
-
-impl From<chrono::DateTime<chrono::Local>> for SavedTime {
-    fn from(value: chrono::DateTime<chrono::Local>) -> Self {
-        Self(value.format("%Y-%m-%d %H:%M:%S").to_string())
-    }
-}
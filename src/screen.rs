@@ -3,6 +3,7 @@ use crate::assets::UiAssets;
 use crate::conf::Conf;
 use crate::draw::UpdateDraw;
 use crate::ui::WindowsOpenState;
+use crate::undo::UndoRedo;
 use crate::{GameSpeed, GameState, GameSystemSet, planet::*};
 use bevy::window::{PrimaryWindow, WindowResized};
 use bevy::{
@@ -485,6 +486,7 @@ fn keyboard_input(
     screen: Res<OccupiedScreenSpace>,
     egui_settings: Query<&bevy_egui::EguiContextSettings, With<bevy::window::PrimaryWindow>>,
     conf: Res<Conf>,
+    mut ew_undo_redo: EventWriter<UndoRedo>,
     mut old_gamespeed: Local<GameSpeed>,
 ) {
     if !wos.dialogs.is_empty() {
@@ -528,6 +530,15 @@ fn keyboard_input(
         wos.animals = false;
         wos.control = false;
     }
+    // Undo by Ctrl+Z, redo by Ctrl+Y or Ctrl+Shift+Z
+    if keys.pressed(KeyCode::ControlLeft) || keys.pressed(KeyCode::ControlRight) {
+        let shift = keys.pressed(KeyCode::ShiftLeft) || keys.pressed(KeyCode::ShiftRight);
+        if keys.just_pressed(KeyCode::KeyY) || (shift && keys.just_pressed(KeyCode::KeyZ)) {
+            ew_undo_redo.send(UndoRedo::Redo);
+        } else if keys.just_pressed(KeyCode::KeyZ) {
+            ew_undo_redo.send(UndoRedo::Undo);
+        }
+    }
     // Debug by Alt+F12
     if keys.just_pressed(KeyCode::F12)
         && (keys.pressed(KeyCode::AltLeft) || keys.pressed(KeyCode::AltRight))
@@ -0,0 +1,616 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+use fnv::FnvHashSet;
+use geom::{Array2d, Coords};
+
+use crate::GameSpeed;
+use crate::conf::{Conf, HighLow3};
+use crate::planet::debug::PlanetDebug;
+use crate::planet::*;
+use crate::screen::CauseEventKind;
+
+/// Time spent advancing the simulation in a frame on the main thread
+#[cfg(target_arch = "wasm32")]
+const POLL_TIME_BUDGET: Duration = Duration::from_millis(12);
+
+/// Commands sent to the simulation worker
+#[allow(clippy::large_enum_variant)]
+#[derive(Clone, Debug)]
+pub enum WorkerCommand {
+    /// Start simulating a new planet
+    Start {
+        planet: Box<Planet>,
+        replay: Option<Replay>,
+    },
+    /// Restore the planet saved in the undo history
+    Restore(Box<Planet>),
+    Action(PlayerAction),
+    /// Edit the planet by debug tools
+    Debug(DebugCommand),
+    SetParams(Box<Params>),
+    SetSpeed(GameSpeed, WorkerTiming),
+    ResetProfiler,
+    /// Advance as fast as possible until the conditions are met
+    FastForward(FastForward),
+    CancelFastForward,
+}
+
+/// Planet edits by debug tools. They are not recorded in the journal
+#[derive(Clone, Debug)]
+pub enum DebugCommand {
+    EditBiome(Coords, Biome),
+    ChangeHeight(Coords, f32),
+    PlaceSettlement(Coords, Settlement),
+    CauseEvent(Coords, CauseEventKind),
+    MaxResources,
+    DeleteCivilization,
+    DeleteAnimals,
+    SetBasics(Basics),
+    SetGasMass(GasKind, f32),
+    SetAerosol(f32),
+    SetWaterVolume(f32),
+}
+
+impl DebugCommand {
+    fn apply(self, planet: &mut Planet, sim: &mut Sim, params: &Params) {
+        match self {
+            Self::EditBiome(p, biome) => planet.edit_biome(p, biome),
+            Self::ChangeHeight(p, value) => planet.change_height(p, value, sim, params),
+            Self::PlaceSettlement(p, settlement) => planet.place_settlement(p, settlement),
+            Self::CauseEvent(p, kind) => match kind {
+                CauseEventKind::Decadence => planet.cause_decadence(p, sim, params),
+                CauseEventKind::CivilWar => planet.cause_civil_war(p, sim, params),
+                CauseEventKind::NuclearExplsion => planet.cause_nuclear_explosion(p, sim, params),
+            },
+            Self::MaxResources => planet.res.debug_max(),
+            Self::DeleteCivilization => planet.delete_civilization(),
+            Self::DeleteAnimals => planet.delete_animals(),
+            Self::SetBasics(basics) => planet.basics = basics,
+            Self::SetGasMass(kind, value) => planet.atmo.set_mass(kind, value),
+            Self::SetAerosol(value) => {
+                planet.atmo.aerosol = value;
+                for tile in planet.map.iter_mut() {
+                    tile.aerosol = value;
+                }
+            }
+            Self::SetWaterVolume(value) => planet.water.water_volume = value,
+        }
+    }
+}
+
+/// Timing settings taken from `Conf`
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct WorkerTiming {
+    slow: Duration,
+    medium: Duration,
+    publish_interval: Duration,
+    report_lifespan: u64,
+}
+
+impl WorkerTiming {
+    pub fn new(conf: &Conf) -> Self {
+        let refresh_frame = match conf.screen_refresh_rate {
+            HighLow3::Low => 7,
+            HighLow3::Medium => 15,
+            HighLow3::High => 30,
+        };
+        Self {
+            slow: Duration::from_millis(conf.slow_speed_sim_duration_ms),
+            medium: Duration::from_millis(conf.medium_speed_sim_duration_ms),
+            publish_interval: Duration::from_millis(1000 / refresh_frame),
+            report_lifespan: conf.report_lifespan,
+        }
+    }
+}
+
+/// Simulation state published by the worker
+pub struct Snapshot {
+    pub planet: Planet,
+    pub sim: SimView,
+    /// The number of commands processed before this snapshot
+    pub n_commands: u64,
+    pub replaying: bool,
+    pub fast_forwarding: bool,
+    /// The reason the last fast-forward stopped
+    pub fast_forward_stop: Option<FastForwardStop>,
+}
+
+impl Snapshot {
+    /// Put the snapshot into the slot. Achievements of the replaced snapshot are carried over
+    fn publish(mut self, slot: &mut Option<Snapshot>) {
+        if let Some(replaced) = slot.take() {
+            self.sim.new_achievements.extend(replaced.sim.new_achievements);
+        }
+        *slot = Some(self);
+    }
+}
+
+/// Part of `Sim` used by the main thread for drawing and achievements
+pub struct SimView {
+    domain: Array2d<Option<(AnimalId, f32)>>,
+    wind: Array2d<(f32, f32)>,
+    ocean_current: Array2d<(f32, f32)>,
+    humidity: Array2d<f32>,
+    albedo: Array2d<f32>,
+    energy_eff: Array2d<f32>,
+    biomass_consumption: Array2d<f32>,
+    profiler: Profiler,
+    new_achievements: FnvHashSet<Achivement>,
+}
+
+impl SimView {
+    /// Copy the view from the worker sim. Achievements are moved to the view
+    fn new(sim: &mut Sim) -> Self {
+        Self {
+            domain: sim.domain.clone(),
+            wind: sim.wind.clone(),
+            ocean_current: sim.ocean_current.clone(),
+            humidity: sim.humidity.clone(),
+            albedo: sim.albedo.clone(),
+            energy_eff: sim.energy_eff.clone(),
+            biomass_consumption: sim.biomass_consumption.clone(),
+            profiler: sim.profiler.clone(),
+            new_achievements: std::mem::take(&mut sim.new_achievements),
+        }
+    }
+
+    /// Update the sim on the main thread
+    pub fn apply(self, sim: &mut Sim) {
+        sim.domain = self.domain;
+        sim.wind = self.wind;
+        sim.ocean_current = self.ocean_current;
+        sim.humidity = self.humidity;
+        sim.albedo = self.albedo;
+        sim.energy_eff = self.energy_eff;
+        sim.biomass_consumption = self.biomass_consumption;
+        sim.profiler = self.profiler;
+        sim.new_achievements.extend(self.new_achievements);
+    }
+}
+
+/// Fast-forward state on the main thread
+#[derive(Clone, Default, Debug, Resource)]
+pub struct FastForwardState {
+    /// Running fast-forward and its start cycles
+    pub running: Option<(FastForward, u64)>,
+    pub last_stop: Option<FastForwardStop>,
+}
+
+/// Owns the planet and advances it according to the game speed
+struct Runner {
+    planet: Planet,
+    sim: Sim,
+    params: Params,
+    replay: Option<Replay>,
+    speed: GameSpeed,
+    timing: Option<WorkerTiming>,
+    n_commands: u64,
+    last_advance: Duration,
+    last_publish: Duration,
+    fast_forward: Option<(FastForward, u64)>,
+    fast_forward_stop: Option<FastForwardStop>,
+}
+
+impl Runner {
+    fn new(params: Params, planet: Planet, replay: Option<Replay>) -> Self {
+        let mut sim = Sim::new(&planet, &params);
+        let mut planet = planet;
+        planet.update(&mut sim, &params);
+        Self {
+            planet,
+            sim,
+            params,
+            replay,
+            speed: GameSpeed::Paused,
+            timing: None,
+            n_commands: 0,
+            last_advance: Duration::ZERO,
+            last_publish: Duration::ZERO,
+            fast_forward: None,
+            fast_forward_stop: None,
+        }
+    }
+
+    fn handle(&mut self, command: WorkerCommand) {
+        self.n_commands += 1;
+        match command {
+            WorkerCommand::Start { planet, replay } => {
+                self.set_planet(*planet);
+                self.replay = replay;
+                self.fast_forward = None;
+            }
+            WorkerCommand::Restore(planet) => {
+                self.set_planet(*planet);
+            }
+            WorkerCommand::Action(action) => {
+                if !self.planet.apply_action(&action, &mut self.sim, &self.params) {
+                    log::warn!("cannot apply action in worker: {:?}", action);
+                }
+            }
+            WorkerCommand::Debug(command) => {
+                command.apply(&mut self.planet, &mut self.sim, &self.params);
+                self.planet.journal.invalidate();
+                self.reset_sim();
+            }
+            WorkerCommand::SetParams(params) => {
+                self.params = *params;
+                self.reset_sim();
+            }
+            WorkerCommand::SetSpeed(speed, timing) => {
+                self.speed = speed;
+                self.timing = Some(timing);
+            }
+            WorkerCommand::ResetProfiler => {
+                self.sim.profiler.reset();
+            }
+            WorkerCommand::FastForward(fast_forward) => {
+                self.fast_forward = Some((fast_forward, self.planet.cycles));
+                self.fast_forward_stop = None;
+            }
+            WorkerCommand::CancelFastForward => {
+                if self.fast_forward.take().is_some() {
+                    self.stop_fast_forward(FastForwardStop::Cancelled);
+                }
+            }
+        }
+    }
+
+    fn set_planet(&mut self, planet: Planet) {
+        self.planet = planet;
+        self.reset_sim();
+    }
+
+    /// Recreate the sim for the current planet and params, keeping the profiler
+    fn reset_sim(&mut self) {
+        let profiler = std::mem::take(&mut self.sim.profiler);
+        self.sim = Sim::new(&self.planet, &self.params);
+        self.sim.profiler = profiler;
+        self.planet.update(&mut self.sim, &self.params);
+    }
+
+    /// Interval between advances. `None` if paused
+    fn interval(&self) -> Option<Duration> {
+        if self.fast_forward.is_some() {
+            return Some(Duration::ZERO);
+        }
+        let timing = self.timing?;
+        match self.speed {
+            GameSpeed::Paused => None,
+            GameSpeed::Slow => Some(timing.slow),
+            GameSpeed::Medium => Some(timing.medium),
+            GameSpeed::Fast => Some(Duration::ZERO),
+        }
+    }
+
+    /// Time until the next advance. `None` if paused
+    fn wait_time(&self, now: Duration) -> Option<Duration> {
+        self.interval()
+            .map(|interval| (self.last_advance + interval).saturating_sub(now))
+    }
+
+    fn advance(&mut self, now: Duration) {
+        crate::planet::debug::clear_tile_logs();
+        if let Some(replay) = &mut self.replay {
+            replay.apply_actions(&mut self.planet, &mut self.sim, &self.params);
+            if replay.finished() {
+                log::info!("replay finished at cycles {}", self.planet.cycles);
+                self.replay = None;
+            }
+        }
+        self.planet.advance(&mut self.sim, &self.params);
+        let report_lifespan = self.timing.map(|timing| timing.report_lifespan);
+        self.planet
+            .monitor(&self.params, report_lifespan.unwrap_or_default());
+        self.last_advance = now;
+
+        if let Some((fast_forward, start_cycles)) = &self.fast_forward
+            && let Some(stop) = fast_forward.check(&self.planet, *start_cycles)
+        {
+            log::info!(
+                "fast-forward stopped at cycles {}: {:?}",
+                self.planet.cycles,
+                stop
+            );
+            self.fast_forward = None;
+            self.stop_fast_forward(stop);
+        }
+    }
+
+    fn stop_fast_forward(&mut self, stop: FastForwardStop) {
+        self.speed = GameSpeed::Paused;
+        self.fast_forward_stop = Some(stop);
+    }
+
+    /// Snapshots are not published while fast-forwarding to suppress drawing
+    fn publish_suppressed(&self) -> bool {
+        self.fast_forward.is_some()
+    }
+
+    /// Cycles advanced by the running fast-forward
+    fn fast_forward_progress(&self) -> u64 {
+        self.fast_forward
+            .as_ref()
+            .map(|(_, start_cycles)| self.planet.cycles - start_cycles)
+            .unwrap_or_default()
+    }
+
+    fn publish_due(&self, now: Duration) -> bool {
+        self.timing
+            .is_some_and(|timing| now - self.last_publish >= timing.publish_interval)
+    }
+
+    fn snapshot(&mut self, now: Duration) -> Snapshot {
+        self.last_publish = now;
+        Snapshot {
+            planet: self.planet.clone(),
+            sim: SimView::new(&mut self.sim),
+            n_commands: self.n_commands,
+            replaying: self.replay.is_some(),
+            fast_forwarding: self.fast_forward.is_some(),
+            fast_forward_stop: self.fast_forward_stop.clone(),
+        }
+    }
+}
+
+/// Handle to the simulation worker. The worker runs on a dedicated thread on native platforms
+#[derive(Default, Resource)]
+pub struct SimWorker {
+    #[cfg(not(target_arch = "wasm32"))]
+    worker: Option<native::Worker>,
+    #[cfg(target_arch = "wasm32")]
+    worker: Option<Runner>,
+    /// The number of commands sent to the worker
+    n_sent: u64,
+    speed: Option<(GameSpeed, WorkerTiming)>,
+    /// Achievements in dropped snapshots, delivered with the next snapshot
+    pending_achievements: FnvHashSet<Achivement>,
+    #[cfg(target_arch = "wasm32")]
+    snapshot: Option<Snapshot>,
+}
+
+impl SimWorker {
+    /// Start simulation of the planet, spawning the worker at the first time
+    pub fn start(&mut self, planet: &Planet, replay: Option<Replay>, params: &Params) {
+        self.speed = None;
+        if self.worker.is_none() {
+            #[cfg(not(target_arch = "wasm32"))]
+            {
+                self.worker = Some(native::Worker::spawn(Runner::new(
+                    params.clone(),
+                    planet.clone(),
+                    replay,
+                )));
+            }
+            #[cfg(target_arch = "wasm32")]
+            {
+                self.worker = Some(Runner::new(params.clone(), planet.clone(), replay));
+            }
+            self.n_sent = 0;
+        } else {
+            self.send(WorkerCommand::Start {
+                planet: Box::new(planet.clone()),
+                replay,
+            });
+        }
+    }
+
+    pub fn send(&mut self, command: WorkerCommand) {
+        let Some(worker) = &mut self.worker else {
+            return;
+        };
+        self.n_sent += 1;
+        #[cfg(not(target_arch = "wasm32"))]
+        worker.send(command);
+        #[cfg(target_arch = "wasm32")]
+        worker.handle(command);
+    }
+
+    /// Send the action to the worker if it is applicable to the last received planet
+    pub fn apply_action(&mut self, action: PlayerAction, planet: &Planet, params: &Params) -> bool {
+        if planet.action_applicable(&action, params) {
+            self.send(WorkerCommand::Action(action));
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Send control values changed by the player
+    pub fn send_control(&mut self, planet: &Planet) {
+        self.send(WorkerCommand::Action(PlayerAction::Control(
+            planet.control_values(),
+        )));
+    }
+
+    pub fn debug(&mut self, command: DebugCommand) {
+        self.send(WorkerCommand::Debug(command));
+    }
+
+    /// Send the planet restored from the undo history
+    pub fn restore_planet(&mut self, planet: &Planet) {
+        self.send(WorkerCommand::Restore(Box::new(planet.clone())));
+    }
+
+    pub fn set_params(&mut self, params: &Params) {
+        self.send(WorkerCommand::SetParams(Box::new(params.clone())));
+    }
+
+    pub fn set_speed(&mut self, speed: GameSpeed, timing: WorkerTiming) {
+        if self.speed != Some((speed, timing)) {
+            self.speed = Some((speed, timing));
+            self.send(WorkerCommand::SetSpeed(speed, timing));
+        }
+    }
+
+    pub fn fast_forward(&mut self, fast_forward: FastForward) {
+        self.send(WorkerCommand::FastForward(fast_forward));
+    }
+
+    pub fn cancel_fast_forward(&mut self) {
+        self.send(WorkerCommand::CancelFastForward);
+    }
+
+    /// Cycles advanced by the running fast-forward
+    pub fn fast_forward_progress(&self) -> u64 {
+        self.worker
+            .as_ref()
+            .map(|worker| worker.fast_forward_progress())
+            .unwrap_or_default()
+    }
+
+    /// Take the latest snapshot. Snapshots before all sent commands are processed are dropped,
+    /// and their achievements are delivered with the next snapshot
+    pub fn receive(&mut self) -> Option<Snapshot> {
+        #[cfg(not(target_arch = "wasm32"))]
+        let mut snapshot = self.worker.as_ref()?.take_snapshot()?;
+        #[cfg(target_arch = "wasm32")]
+        let mut snapshot = self.snapshot.take()?;
+
+        let new_achievements = &mut snapshot.sim.new_achievements;
+        if snapshot.n_commands < self.n_sent {
+            self.pending_achievements.extend(new_achievements.drain());
+            return None;
+        }
+        new_achievements.extend(self.pending_achievements.drain());
+        Some(snapshot)
+    }
+
+    /// Advance the simulation on the main thread
+    #[cfg(target_arch = "wasm32")]
+    pub fn poll(&mut self, now: Duration) {
+        let Some(runner) = &mut self.worker else {
+            return;
+        };
+        let n_commands_published = self
+            .snapshot
+            .as_ref()
+            .map(|snapshot| snapshot.n_commands)
+            .unwrap_or_default();
+        let mut publish = runner.n_commands > n_commands_published;
+        // Advance multiple cycles in a frame while fast-forwarding or at fast speed
+        let start = bevy::utils::Instant::now();
+        while runner.wait_time(now).is_some_and(|wait| wait.is_zero()) {
+            let fast_forwarding = runner.publish_suppressed();
+            runner.advance(now);
+            publish |= if fast_forwarding {
+                !runner.publish_suppressed()
+            } else {
+                runner.publish_due(now)
+            };
+            if start.elapsed() >= POLL_TIME_BUDGET {
+                break;
+            }
+        }
+        if publish {
+            runner.snapshot(now).publish(&mut self.snapshot);
+        }
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+mod native {
+    use std::sync::atomic::{AtomicU64, Ordering};
+    use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender, TryRecvError};
+    use std::sync::{Arc, Mutex};
+    use std::time::Instant;
+
+    use super::*;
+
+    pub struct Worker {
+        tx: Sender<WorkerCommand>,
+        snapshot: Arc<Mutex<Option<Snapshot>>>,
+        progress: Arc<AtomicU64>,
+    }
+
+    /// Data shared between the worker thread and the main thread
+    struct Shared {
+        snapshot: Arc<Mutex<Option<Snapshot>>>,
+        progress: Arc<AtomicU64>,
+    }
+
+    impl Worker {
+        pub fn spawn(runner: Runner) -> Self {
+            let (tx, rx) = mpsc::channel();
+            let snapshot = Arc::new(Mutex::new(None));
+            let progress = Arc::new(AtomicU64::new(0));
+            let shared = Shared {
+                snapshot: snapshot.clone(),
+                progress: progress.clone(),
+            };
+            std::thread::Builder::new()
+                .name("sim-worker".into())
+                .spawn(move || run(runner, rx, shared))
+                .expect("cannot spawn simulation worker thread");
+            Self {
+                tx,
+                snapshot,
+                progress,
+            }
+        }
+
+        pub fn send(&self, command: WorkerCommand) {
+            if self.tx.send(command).is_err() {
+                log::error!("simulation worker has stopped");
+            }
+        }
+
+        pub fn take_snapshot(&self) -> Option<Snapshot> {
+            self.snapshot.lock().unwrap().take()
+        }
+
+        pub fn fast_forward_progress(&self) -> u64 {
+            self.progress.load(Ordering::Relaxed)
+        }
+    }
+
+    fn run(mut runner: Runner, rx: Receiver<WorkerCommand>, shared: Shared) {
+        let start = Instant::now();
+
+        loop {
+            // Wait for commands until the next advance
+            let received = match runner.wait_time(start.elapsed()) {
+                None => rx.recv().map_err(|_| RecvTimeoutError::Disconnected),
+                Some(wait) if wait.is_zero() => rx.try_recv().map_err(|e| match e {
+                    TryRecvError::Empty => RecvTimeoutError::Timeout,
+                    TryRecvError::Disconnected => RecvTimeoutError::Disconnected,
+                }),
+                Some(wait) => rx.recv_timeout(wait),
+            };
+
+            match received {
+                Ok(command) => {
+                    runner.handle(command);
+                    for command in rx.try_iter() {
+                        runner.handle(command);
+                    }
+                    shared
+                        .progress
+                        .store(runner.fast_forward_progress(), Ordering::Relaxed);
+                    let snapshot = runner.snapshot(start.elapsed());
+                    snapshot.publish(&mut shared.snapshot.lock().unwrap());
+                }
+                Err(RecvTimeoutError::Timeout) => {
+                    let now = start.elapsed();
+                    let fast_forwarding = runner.publish_suppressed();
+                    runner.advance(now);
+                    shared
+                        .progress
+                        .store(runner.fast_forward_progress(), Ordering::Relaxed);
+                    if fast_forwarding {
+                        // Publish only when the fast-forward has stopped
+                        if !runner.publish_suppressed() {
+                            let snapshot = runner.snapshot(now);
+                            snapshot.publish(&mut shared.snapshot.lock().unwrap());
+                        }
+                    } else if runner.publish_due(now) && shared.snapshot.lock().unwrap().is_none() {
+                        // Skip publishing while the previous snapshot is not taken
+                        let snapshot = runner.snapshot(now);
+                        snapshot.publish(&mut shared.snapshot.lock().unwrap());
+                    }
+                }
+                Err(RecvTimeoutError::Disconnected) => return,
+            }
+        }
+    }
+}
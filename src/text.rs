@@ -39,6 +39,7 @@ impl Report {
             ReportContent::WarnLowTemp => (Warn, t!("report/warn-low-temp")),
             ReportContent::WarnLowOxygen => (Warn, t!("report/warn-low-oxygen")),
             ReportContent::WarnLowCarbonDioxide => (Warn, t!("report/warn-low-carbon-dioxide")),
+            ReportContent::WarnRunawayGreenhouse => (Warn, t!("report/warn-runaway-greenhouse")),
             ReportContent::EventAnimalBorn { animal, .. } => {
                 let animal = t!("animal", animal);
                 (Notice, t!("report/animal-born"; animal = animal))
@@ -74,6 +75,8 @@ impl Report {
             ReportContent::EventExodusCompleted { name, .. } => {
                 (Notice, t!("report/exodus-completed"; civ = name))
             }
+            ReportContent::EventPlague { .. } => (Notice, t!("report/plague")),
+            ReportContent::EventJournalDiscarded { .. } => (Warn, t!("report/journal-discarded")),
         }
     }
 }
@@ -10,6 +10,8 @@ use std::collections::HashMap;
 use std::sync::{LazyLock, RwLock};
 use strum::{AsRefStr, EnumIter};
 
+use crate::planet::Planet;
+
 #[derive(Clone, Copy, Debug)]
 pub struct TextAssetsPlugin;
 
@@ -18,7 +20,11 @@ impl Plugin for TextAssetsPlugin {
         app.init_asset::<TranslationText>()
             .register_asset_loader(TextLoader)
             .init_asset::<RandomNameList>()
-            .register_asset_loader(RandomNameListLoader);
+            .register_asset_loader(RandomNameListLoader)
+            .add_systems(
+                Update,
+                update_species_names.run_if(resource_exists::<Planet>),
+            );
     }
 }
 
@@ -73,6 +79,9 @@ macro_rules! t {
 static LANG: AtomicCell<Lang> = AtomicCell::new(Lang::English);
 static TRANSLATION_TEXTS: LazyLock<RwLock<HashMap<Lang, TranslationText>>> =
     LazyLock::new(|| RwLock::new(HashMap::default()));
+/// Names of species generated on the current planet, used for animal and civilization names
+static SPECIES_NAMES: LazyLock<RwLock<HashMap<String, String>>> =
+    LazyLock::new(|| RwLock::new(HashMap::default()));
 
 pub fn set_lang(lang: Lang) {
     LANG.store(lang);
@@ -146,9 +155,34 @@ pub fn get_text<S: AsRef<str>>(s: S, map: HashMap<String, String>) -> String {
             return replace(text, map);
         }
     }
+    if let Some(("animal" | "civ", id)) = s.split_once('/')
+        && let Some(name) = SPECIES_NAMES.read().unwrap().get(id)
+    {
+        return name.clone();
+    }
     s.into()
 }
 
+fn update_species_names(planet: Res<Planet>) {
+    if !planet.is_changed() {
+        return;
+    }
+    let species_names = &mut SPECIES_NAMES.write().unwrap();
+    if species_names.len() == planet.species.len()
+        && planet
+            .species
+            .iter()
+            .all(|(id, species)| species_names.get(id.as_str()) == Some(&species.name))
+    {
+        return;
+    }
+    **species_names = planet
+        .species
+        .iter()
+        .map(|(id, species)| (id.to_string(), species.name.clone()))
+        .collect();
+}
+
 pub fn replace(s: &str, map: HashMap<String, String>) -> String {
     static RE: LazyLock<Regex> =
         LazyLock::new(|| Regex::new(r"\{\$([a-zA-Z][a-zA-Z0-9_-]*)\}").unwrap());
@@ -1,5 +1,3 @@
-use std::sync::atomic::AtomicBool;
-
 use bevy::prelude::*;
 use serde::{Deserialize, Serialize};
 use strum::{AsRefStr, Display, EnumDiscriminants};
@@ -8,8 +6,6 @@ use crate::{manage_planet::SaveState, planet::*};
 
 pub const TUTORIAL_PLANET: &str = "tutorial";
 
-pub static CIVILIZEABLE: AtomicBool = AtomicBool::new(true);
-
 #[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize, Resource)]
 pub struct TutorialState {
     current: TutorialStep,
@@ -195,27 +191,27 @@ impl TutorialStep {
 fn check(planet: &Planet, item: ChecklistItem) -> bool {
     let requirement = match item {
         ChecklistItem::PowerChecklist1 => Requirement::SpaceBuildingBuilt {
-            kind: SpaceBuildingKind::FusionReactor,
+            kind: SpaceBuildingKind::new("fusion-reactor"),
             n: 5,
         },
         ChecklistItem::PowerChecklist2 => Requirement::SpaceBuildingBuilt {
-            kind: SpaceBuildingKind::AsteroidMiningStation,
+            kind: SpaceBuildingKind::new("asteroid-mining-station"),
             n: 5,
         },
         ChecklistItem::FertilizeChecklist1 => Requirement::StructureBuilt {
-            kind: StructureKind::FertilizationPlant,
+            kind: StructureKind::new("fertilization-plant"),
             n: 3,
         },
         ChecklistItem::FertilizeChecklist2 => Requirement::BiomeTiles {
-            biomes: vec![Biome::Grassland],
+            biomes: vec![Biome::new("grassland")],
             n: 10,
         },
         ChecklistItem::BuildOxygenChecklist1 => Requirement::SpaceBuildingBuilt {
-            kind: SpaceBuildingKind::DysonSwarmUnit,
+            kind: SpaceBuildingKind::new("dyson-swarm-unit"),
             n: 5,
         },
         ChecklistItem::BuildOxygenChecklist2 => Requirement::StructureBuilt {
-            kind: StructureKind::OxygenGenerator,
+            kind: StructureKind::new("oxygen-generator"),
             n: 8,
         },
         ChecklistItem::WaitOxygenChecklist1 => Requirement::PartialPressureHigherThan {
@@ -224,14 +220,14 @@ fn check(planet: &Planet, item: ChecklistItem) -> bool {
         },
         ChecklistItem::WaitOxygenChecklist2 => Requirement::BiomeTiles {
             biomes: vec![
-                Biome::BorealForest,
-                Biome::TemperateForest,
-                Biome::TropicalRainforest,
+                Biome::new("boreal-forest"),
+                Biome::new("temperate-forest"),
+                Biome::new("tropical-rainforest"),
             ],
             n: 50,
         },
         ChecklistItem::CarbonChecklist1 => Requirement::StructureBuilt {
-            kind: StructureKind::CarbonCapturer,
+            kind: StructureKind::new("carbon-capturer"),
             n: 2,
         },
         ChecklistItem::AnimalChecklist1 => Requirement::AnimalTiles { n: 30 },
@@ -240,7 +236,7 @@ fn check(planet: &Planet, item: ChecklistItem) -> bool {
             animal_id: None,
         },
         ChecklistItem::OrbitalMirrorChecklist1 => Requirement::SpaceBuildingBuilt {
-            kind: SpaceBuildingKind::OrbitalMirror,
+            kind: SpaceBuildingKind::new("orbital-mirror"),
             n: 1,
         },
         ChecklistItem::ControlCivChecklist1 => Requirement::CivPopGrowthAdjust { range: 150..=200 },
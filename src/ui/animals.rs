@@ -8,6 +8,8 @@ use compact_str::format_compact;
 pub struct State {
     ordered_ids: Vec<AnimalId>,
     current: AnimalId,
+    food_web: FoodWeb,
+    n_species: usize,
     civ_default_name: String,
     civ_name: String,
 }
@@ -26,8 +28,13 @@ pub fn animals_window(
     if !wos.animals {
         return;
     }
-    if state.is_none() {
-        *state = Some(State::new(&params));
+    if state.as_ref().is_none_or(|state| {
+        state.n_species != planet.species.len()
+            || !(params.animals.contains_key(&state.current)
+                || planet.species.contains_key(&state.current))
+    }) {
+        let current = state.as_ref().map(|state| state.current);
+        *state = Some(State::new(&planet, &params, current));
     }
     let state = state.as_mut().unwrap();
 
@@ -74,22 +81,29 @@ pub fn animals_window(
 fn contents(
     ui: &mut egui::Ui,
     state: &mut State,
-    _planet: &Planet,
+    planet: &Planet,
     params: &Params,
     textures: &UiTextures,
     cursor_mode: &mut CursorMode,
     se_player: &SoundEffectPlayer,
 ) {
     ui.horizontal(|ui| {
-        ui.add(egui::Image::new(
-            textures.get(format_compact!("animals/{}", state.current)),
-        ));
+        ui.add(egui::Image::new(textures.get(format_compact!(
+            "animals/{}",
+            planet.origin_species(state.current)
+        ))));
         ui.heading(t!("animal", state.current));
     });
 
-    let attr = params.animals.get(&state.current).unwrap();
+    let attr = planet.animal_attr(&state.current, params);
 
     egui::Grid::new("table_atmo").striped(true).show(ui, |ui| {
+        if let Some(species) = planet.species.get(&state.current) {
+            ui.label(t!("branched-from"));
+            ui.label(t!("animal", species.parent));
+            ui.end_row();
+        }
+
         ui.label(t!("size"));
         ui.label(t!(attr.size));
         ui.end_row();
@@ -118,6 +132,22 @@ fn contents(
         ui.label(s);
         ui.end_row();
 
+        ui.label(t!("diet"));
+        let s = attr.diet.iter().fold(String::new(), |s, diet| {
+            let name = match diet {
+                Diet::Biomass => t!("biomass"),
+                Diet::Animal(id) => t!("animal", id),
+                Diet::Size(size) => t!(size),
+            };
+            if s.is_empty() {
+                name
+            } else {
+                format!("{}, {}", s, name)
+            }
+        });
+        ui.label(s);
+        ui.end_row();
+
         ui.label(t!("livable-temperature"));
         ui.label(format!(
             "{}°C - {}°C",
@@ -126,18 +156,77 @@ fn contents(
         ));
         ui.end_row();
 
+        ui.label(t!("growth-speed"));
+        ui.label(format!("{:.0}%", attr.growth_speed * 100.0));
+        ui.end_row();
+
         ui.label(t!("civ-probability"));
         ui.label(format!("{:.0}%", attr.civ_prob * 100.0));
         ui.end_row();
     });
 
     ui.separator();
+    ui.label(t!("food-web"));
+    food_web(ui, state, planet, attr, textures, se_player);
+
+    // Generated species cannot be spawned by the player
+    if params.animals.contains_key(&state.current) {
+        ui.separator();
+        ui.horizontal(|ui| {
+            if ui.button(t!("spawn")).clicked() {
+                *cursor_mode = CursorMode::SpawnAnimal(state.current);
+                se_player.play("select-item");
+            }
+        });
+    }
+}
+
+fn food_web(
+    ui: &mut egui::Ui,
+    state: &mut State,
+    planet: &Planet,
+    attr: &AnimalAttr,
+    textures: &UiTextures,
+    se_player: &SoundEffectPlayer,
+) {
+    let mut selected = None;
+    let mut animal_list = |ui: &mut egui::Ui, ids: &[AnimalId]| {
+        ui.vertical(|ui| {
+            for id in ids {
+                if ui
+                    .add(egui::ImageButton::new(textures.get(format_compact!(
+                        "animals/{}",
+                        planet.origin_species(*id)
+                    ))))
+                    .on_hover_text(t!("animal", id))
+                    .clicked()
+                {
+                    selected = Some(*id);
+                }
+            }
+        });
+    };
+
     ui.horizontal(|ui| {
-        if ui.button(t!("spawn")).clicked() {
-            *cursor_mode = CursorMode::SpawnAnimal(state.current);
-            se_player.play("select-item");
-        }
+        ui.vertical(|ui| {
+            if attr.diet.contains(&Diet::Biomass) {
+                ui.label(t!("biomass"));
+            }
+            animal_list(ui, state.food_web.prey(&state.current));
+        });
+        ui.label("→");
+        ui.add(egui::Image::new(textures.get(format_compact!(
+            "animals/{}",
+            planet.origin_species(state.current)
+        ))));
+        ui.label("→");
+        animal_list(ui, state.food_web.predators(&state.current));
     });
+
+    if let Some(id) = selected {
+        state.select(id);
+        se_player.play("select-item");
+    }
 }
 
 fn select_panel(ui: &mut egui::Ui, state: &mut State, se_player: &SoundEffectPlayer) {
@@ -161,21 +250,34 @@ fn select_panel(ui: &mut egui::Ui, state: &mut State, se_player: &SoundEffectPla
 
     // Selected animal changed
     if before != state.current {
-        state.civ_default_name = t!("civ", state.current);
-        state.civ_name = state.civ_default_name.clone();
+        state.select(state.current);
     }
 }
 
 impl State {
-    fn new(params: &Params) -> Self {
+    fn new(planet: &Planet, params: &Params, current: Option<AnimalId>) -> Self {
         let mut ids: Vec<_> = params.animals.keys().cloned().collect();
         ids.sort_unstable();
-        let current = ids[0];
+        // Generated species follow the species defined in assets
+        let mut species_ids: Vec<_> = planet.species.keys().cloned().collect();
+        species_ids.sort_unstable_by_key(|id| &planet.species[id].name);
+        ids.extend(species_ids);
+        let current = current
+            .filter(|current| ids.contains(current))
+            .unwrap_or(ids[0]);
         Self {
             ordered_ids: ids,
             current,
+            food_web: FoodWeb::new(planet, params),
+            n_species: planet.species.len(),
             civ_default_name: String::new(),
             civ_name: String::new(),
         }
     }
+
+    fn select(&mut self, id: AnimalId) {
+        self.current = id;
+        self.civ_default_name = t!("civ", id);
+        self.civ_name = self.civ_default_name.clone();
+    }
 }
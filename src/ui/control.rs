@@ -5,10 +5,11 @@ use strum::{AsRefStr, EnumIter, IntoEnumIterator};
 use crate::{
     audio::SoundEffectPlayer,
     planet::{
-        AnimalId, BuildingControlValue, Params, Planet, Requirement, SpaceBuildingKind,
+        AnimalId, BuildingControlValue, Params, Planet, Replay, Requirement, SpaceBuildingKind,
         StructureKind,
     },
     screen::OccupiedScreenSpace,
+    sim_worker::SimWorker,
 };
 
 use super::{HELP_TOOLTIP_WIDTH, UiTextures, WindowsOpenState, help::HelpItem};
@@ -31,11 +32,13 @@ pub fn control_window(
     textures: Res<UiTextures>,
     mut current_panel: Local<Panel>,
     mut current_civ_id: Local<Option<AnimalId>>,
+    (mut worker, replay): (ResMut<SimWorker>, Option<Res<Replay>>),
     se_player: SoundEffectPlayer,
 ) {
     if !wos.control {
         return;
     }
+    let control_values = planet.control_values();
 
     let rect = egui::Window::new("control-window")
         .anchor(
@@ -66,11 +69,16 @@ pub fn control_window(
                 }
             });
             ui.separator();
+            if replay.is_some() {
+                ui.disable();
+            }
 
             egui::ScrollArea::vertical()
                 .auto_shrink(egui::Vec2b::new(false, false))
                 .show(ui, |ui| match *current_panel {
-                    Panel::Planet => planet_control(ui, &textures, &mut planet, &se_player),
+                    Panel::Planet => {
+                        planet_control(ui, &textures, &mut planet, &params, &se_player)
+                    }
                     Panel::Civilization => civ_control(
                         ui,
                         &textures,
@@ -85,12 +93,17 @@ pub fn control_window(
         .response
         .rect;
     occupied_screen_space.push_egui_window_rect(rect);
+
+    if planet.control_values() != control_values {
+        worker.send_control(&planet);
+    }
 }
 
 fn planet_control(
     ui: &mut egui::Ui,
     textures: &UiTextures,
     planet: &mut Planet,
+    params: &Params,
     se_player: &SoundEffectPlayer,
 ) {
     ui.style_mut().wrap_mode = Some(egui::TextWrapMode::Wrap);
@@ -102,11 +115,13 @@ fn planet_control(
         ui.image(textures.get("ui/icon-help"))
             .on_hover_text(t!("help/control/orbital-mirror"));
     });
-    let building = planet.space_building_mut(SpaceBuildingKind::OrbitalMirror);
+    let orbital_mirror = SpaceBuildingKind::new("orbital-mirror");
+    let rate_range = params.building_attrs(orbital_mirror).control_rate_range();
+    let building = planet.space_building_mut(orbital_mirror);
     if building.n > 0 {
         if let BuildingControlValue::IncreaseRate(rate) = &mut building.control
             && ui
-                .add(egui::Slider::new(rate, -100..=100).suffix("%"))
+                .add(egui::Slider::new(rate, rate_range).suffix("%"))
                 .changed()
         {
             se_player.play_if_stopped("slider");
@@ -126,7 +141,7 @@ fn planet_control(
             .on_hover_text(t!("help/control/forestation-speed"));
     });
     let requirement = Requirement::StructureBuilt {
-        kind: StructureKind::FertilizationPlant,
+        kind: StructureKind::new("fertilization-plant"),
         n: 1,
     };
     if requirement.check(planet) {
@@ -2,6 +2,7 @@ use super::{CursorMode, OccupiedScreenSpace, WindowsOpenState};
 use crate::planet::*;
 use crate::saveload::SaveState;
 use crate::screen::HoverTile;
+use crate::sim_worker::{DebugCommand, SimWorker, WorkerCommand};
 use crate::{planet::debug::PlanetDebug, screen::CauseEventKind};
 use bevy::prelude::*;
 use bevy_egui::{EguiContexts, egui};
@@ -17,17 +18,19 @@ pub enum Panel {
     Map,
     Planet,
     Atmo,
+    Profile,
 }
 
 pub fn debug_tools_window(
     mut egui_ctxs: EguiContexts,
     mut occupied_screen_space: ResMut<OccupiedScreenSpace>,
-    mut planet: ResMut<Planet>,
+    planet: Res<Planet>,
     mut cursor_mode: ResMut<CursorMode>,
     mut wos: ResMut<WindowsOpenState>,
     mut save_state: ResMut<SaveState>,
     params: Res<Params>,
-    sim: Res<Sim>,
+    mut sim: ResMut<Sim>,
+    mut worker: ResMut<SimWorker>,
     hover_tile: Query<&HoverTile>,
     (mut current_panel, mut map_panel, mut last_hover_tile): (
         Local<Panel>,
@@ -63,6 +66,7 @@ pub fn debug_tools_window(
 
     let p = hover_tile.0.unwrap_or(last_hover_tile.unwrap());
 
+    let mut commands = Vec::new();
     let rect = egui::Window::new("Debug Tools")
         .open(&mut wos.debug_tools)
         .vscroll(true)
@@ -76,16 +80,22 @@ pub fn debug_tools_window(
 
             match *current_panel {
                 Panel::TileInfo => info_ui(ui, &planet, &sim, p),
-                Panel::Sim => sim_ui(ui, &mut planet),
-                Panel::Map => map_panel.ui(ui, &mut planet, &mut cursor_mode, &params),
-                Panel::Planet => planet_ui(ui, &mut planet),
-                Panel::Atmo => atmo_ui(ui, &mut planet),
+                Panel::Sim => sim_ui(ui, &mut commands),
+                Panel::Map => map_panel.ui(ui, &planet, &mut cursor_mode, &params, &mut commands),
+                Panel::Planet => planet_ui(ui, &planet, &mut commands),
+                Panel::Atmo => atmo_ui(ui, &planet, &mut commands),
+                Panel::Profile => profile_ui(ui, &mut sim, &mut worker),
             }
         })
         .unwrap()
         .response
         .rect;
     occupied_screen_space.push_egui_window_rect(rect);
+
+    // Debug edits are applied by the simulation worker
+    for command in commands {
+        worker.debug(command);
+    }
 }
 
 fn info_ui(ui: &mut egui::Ui, planet: &Planet, sim: &Sim, p: Coords) {
@@ -109,9 +119,36 @@ fn info_ui(ui: &mut egui::Ui, planet: &Planet, sim: &Sim, p: Coords) {
     });
 }
 
-fn sim_ui(ui: &mut egui::Ui, planet: &mut Planet) {
+fn sim_ui(ui: &mut egui::Ui, commands: &mut Vec<DebugCommand>) {
     if ui.button("max resources").clicked() {
-        planet.res.debug_max();
+        commands.push(DebugCommand::MaxResources);
+    }
+}
+
+fn profile_ui(ui: &mut egui::Ui, sim: &mut ResMut<Sim>, worker: &mut SimWorker) {
+    let profiler = &sim.profiler;
+    ui.label(format!("{} cycles measured", profiler.n_cycles));
+    egui::Grid::new("profile_grid").striped(true).show(ui, |ui| {
+        ui.strong("stage");
+        ui.strong("last [ms]");
+        ui.strong("average [ms]");
+        ui.strong("max [ms]");
+        ui.end_row();
+        for stage in profiler.stages() {
+            ui.label(stage.name);
+            ui.label(format!("{:.2}", stage.last));
+            ui.label(format!("{:.2}", stage.average));
+            ui.label(format!("{:.2}", stage.max));
+            ui.end_row();
+        }
+        ui.strong("total");
+        ui.label("");
+        ui.label(format!("{:.2}", profiler.total_average()));
+        ui.end_row();
+    });
+    if ui.button("reset").clicked() {
+        sim.profiler.reset();
+        worker.send(WorkerCommand::ResetProfiler);
     }
 }
 
@@ -127,9 +164,10 @@ impl MapPanel {
     fn ui(
         &mut self,
         ui: &mut egui::Ui,
-        planet: &mut Planet,
+        planet: &Planet,
         cursor_mode: &mut CursorMode,
         params: &Params,
+        commands: &mut Vec<DebugCommand>,
     ) {
         let default_civ_animal = *params.animals.iter().next().unwrap().0;
         if self.animal_id.is_none() {
@@ -140,7 +178,7 @@ impl MapPanel {
             egui::ComboBox::from_id_salt("debug_tool_biomes")
                 .selected_text(AsRef::<str>::as_ref(&self.biome))
                 .show_ui(ui, |ui| {
-                    for b in Biome::iter() {
+                    for b in params.biome_list() {
                         ui.selectable_value(&mut self.biome, b, AsRef::<str>::as_ref(&b));
                     }
                 });
@@ -206,11 +244,11 @@ impl MapPanel {
         ui.separator();
 
         if ui.button("delete all civilization").clicked() {
-            planet.delete_civilization();
+            commands.push(DebugCommand::DeleteCivilization);
         }
 
         if ui.button("delete all animals").clicked() {
-            planet.delete_animals();
+            commands.push(DebugCommand::DeleteAnimals);
         }
 
         ui.separator();
@@ -221,25 +259,54 @@ impl MapPanel {
     }
 }
 
-fn planet_ui(ui: &mut egui::Ui, planet: &mut Planet) {
-    ui.add(
-        egui::Slider::new(&mut planet.basics.solar_constant, 0.0..=3000.0)
-            .text(t!("solar-constant")),
-    );
+fn planet_ui(ui: &mut egui::Ui, planet: &Planet, commands: &mut Vec<DebugCommand>) {
+    let mut basics = planet.basics.clone();
+    let mut changed = ui
+        .add(egui::Slider::new(&mut basics.solar_constant, 0.0..=3000.0).text(t!("solar-constant")))
+        .changed();
+    changed |= ui
+        .add(egui::Slider::new(&mut basics.axial_tilt, 0.0..=90.0).text(t!("axial-tilt")))
+        .changed();
+    changed |= ui
+        .add(egui::Slider::new(&mut basics.eccentricity, 0.0..=0.9).text(t!("eccentricity")))
+        .changed();
+    changed |= ui
+        .checkbox(&mut basics.retrograde_rotation, "retrograde rotation")
+        .changed();
+    ui.label(format!("seed {}", planet.seed));
+    if changed {
+        commands.push(DebugCommand::SetBasics(basics));
+    }
 }
 
-fn atmo_ui(ui: &mut egui::Ui, planet: &mut Planet) {
+fn atmo_ui(ui: &mut egui::Ui, planet: &Planet, commands: &mut Vec<DebugCommand>) {
     for gas_kind in GasKind::iter() {
         let mut value = planet.atmo.mass(gas_kind);
-        ui.add(
-            egui::Slider::new(&mut value, 1.0e+5..=1.0e+11)
-                .text(t!(gas_kind))
-                .logarithmic(true),
-        );
-        planet.atmo.set_mass(gas_kind, value);
+        if ui
+            .add(
+                egui::Slider::new(&mut value, 1.0e+5..=1.0e+11)
+                    .text(t!(gas_kind))
+                    .logarithmic(true),
+            )
+            .changed()
+        {
+            commands.push(DebugCommand::SetGasMass(gas_kind, value));
+        }
     }
     ui.label(format!("cloud {:.2}", planet.atmo.cloud_amount));
-    ui.add(egui::Slider::new(&mut planet.atmo.aerosol, 0.0..=100.0).text("aerosol"));
+    let mut aerosol = planet.atmo.aerosol;
+    if ui
+        .add(egui::Slider::new(&mut aerosol, 0.0..=100.0).text("aerosol"))
+        .changed()
+    {
+        commands.push(DebugCommand::SetAerosol(aerosol));
+    }
     ui.separator();
-    ui.add(egui::Slider::new(&mut planet.water.water_volume, 0.0..=1.0e+18).text("water volume"));
+    let mut water_volume = planet.water.water_volume;
+    if ui
+        .add(egui::Slider::new(&mut water_volume, 0.0..=1.0e+18).text("water volume"))
+        .changed()
+    {
+        commands.push(DebugCommand::SetWaterVolume(water_volume));
+    }
 }
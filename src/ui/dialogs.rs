@@ -6,7 +6,9 @@ use crate::{
     audio::SoundEffectPlayer,
     draw::UpdateDraw,
     manage_planet::ManagePlanetError,
-    planet::{Params, Planet},
+    planet::{Params, Planet, PlayerAction},
+    sim_worker::SimWorker,
+    undo::UndoHistory,
 };
 
 pub fn dialogs(
@@ -14,7 +16,9 @@ pub fn dialogs(
     mut occupied_screen_space: ResMut<OccupiedScreenSpace>,
     mut wos: ResMut<WindowsOpenState>,
     mut update_draw: ResMut<UpdateDraw>,
-    mut planet: ResMut<Planet>,
+    planet: Res<Planet>,
+    mut worker: ResMut<SimWorker>,
+    mut history: ResMut<UndoHistory>,
     params: Res<Params>,
     se_player: SoundEffectPlayer,
 ) {
@@ -34,7 +38,8 @@ pub fn dialogs(
             ui.label(t!("animal", id));
             ui.separator();
             if ui.button(t!("ok")).clicked() {
-                planet.civilize_animal(*p, *id, &params);
+                history.record(&planet);
+                worker.apply_action(PlayerAction::Civilize { p: *p, id: *id }, &planet, &params);
                 close = true;
                 update_draw.update();
                 se_player.play_with_priority("civilize", true);
@@ -0,0 +1,258 @@
+use bevy::prelude::*;
+use bevy_egui::{EguiContexts, egui};
+use strum::{AsRefStr, EnumIter, IntoEnumIterator};
+
+use super::{OccupiedScreenSpace, WindowsOpenState};
+use crate::{
+    audio::SoundEffectPlayer,
+    planet::*,
+    sim_worker::{FastForwardState, SimWorker},
+};
+
+/// Kinds of requirements selectable in the fast-forward window
+#[derive(Clone, Copy, PartialEq, Eq, Debug, EnumIter, AsRefStr)]
+#[strum(serialize_all = "kebab-case")]
+enum RequirementKind {
+    PartialPressure,
+    BiomeTiles,
+    AnimalTiles,
+    Settlements,
+    StructureBuilt,
+}
+
+/// Input values of the fast-forward window
+#[derive(Debug)]
+pub struct FastForwardForm {
+    cycles: u64,
+    use_requirement: bool,
+    requirement: RequirementKind,
+    gas: GasKind,
+    partial_pressure: f32,
+    biome: Biome,
+    animal_id: Option<AnimalId>,
+    structure: StructureKind,
+    n: u32,
+    reports: Vec<ReportKind>,
+}
+
+impl Default for FastForwardForm {
+    fn default() -> Self {
+        Self {
+            cycles: 5000,
+            use_requirement: false,
+            requirement: RequirementKind::PartialPressure,
+            gas: GasKind::Oxygen,
+            partial_pressure: 0.2,
+            biome: Biome::default(),
+            animal_id: None,
+            structure: StructureKind::SETTLEMENT,
+            n: 100,
+            reports: Vec::new(),
+        }
+    }
+}
+
+impl FastForwardForm {
+    fn fast_forward(&self) -> FastForward {
+        FastForward {
+            cycles: self.cycles,
+            requirement: self.use_requirement.then(|| self.requirement()),
+            reports: self.reports.clone(),
+        }
+    }
+
+    fn requirement(&self) -> Requirement {
+        match self.requirement {
+            RequirementKind::PartialPressure => Requirement::PartialPressureHigherThan {
+                kind: self.gas,
+                value: self.partial_pressure,
+            },
+            RequirementKind::BiomeTiles => Requirement::BiomeTiles {
+                biomes: vec![self.biome],
+                n: self.n,
+            },
+            RequirementKind::AnimalTiles => Requirement::AnimalTiles { n: self.n },
+            RequirementKind::Settlements => Requirement::Settlements {
+                n: self.n,
+                animal_id: self.animal_id,
+            },
+            RequirementKind::StructureBuilt => Requirement::StructureBuilt {
+                kind: self.structure,
+                n: self.n,
+            },
+        }
+    }
+}
+
+pub fn fast_forward_window(
+    mut egui_ctxs: EguiContexts,
+    mut wos: ResMut<WindowsOpenState>,
+    mut occupied_screen_space: ResMut<OccupiedScreenSpace>,
+    mut state: ResMut<FastForwardState>,
+    mut worker: ResMut<SimWorker>,
+    planet: Res<Planet>,
+    params: Res<Params>,
+    mut form: Local<FastForwardForm>,
+    se_player: SoundEffectPlayer,
+) {
+    if !wos.fast_forward && state.running.is_none() {
+        return;
+    }
+
+    let mut open = true;
+    let rect = egui::Window::new(t!("fast-forward"))
+        .constrain_to(super::misc::constrain_to_rect(
+            egui_ctxs.ctx_mut(),
+            &occupied_screen_space,
+        ))
+        .resizable(egui::Vec2b::new(false, false))
+        .open(&mut open)
+        .show(egui_ctxs.ctx_mut(), |ui| {
+            if let Some((fast_forward, _)) = &state.running {
+                let progress = worker.fast_forward_progress();
+                ui.add(
+                    egui::ProgressBar::new(progress as f32 / fast_forward.cycles.max(1) as f32)
+                        .text(format!("{} / {}", progress, fast_forward.cycles)),
+                );
+                if ui.button(t!("cancel")).clicked() {
+                    worker.cancel_fast_forward();
+                    se_player.play("select-item");
+                }
+                return;
+            }
+
+            form_ui(ui, &mut form, &params);
+
+            ui.separator();
+            if ui.button(t!("start")).clicked() {
+                let fast_forward = form.fast_forward();
+                worker.fast_forward(fast_forward.clone());
+                state.running = Some((fast_forward, planet.cycles));
+                state.last_stop = None;
+                se_player.play("select-item");
+            }
+
+            if let Some(stop) = &state.last_stop {
+                ui.separator();
+                match stop {
+                    FastForwardStop::Report(report) => {
+                        ui.label(report.text().1);
+                    }
+                    _ => {
+                        ui.label(t!("fast_forward_stop", stop.as_ref()));
+                    }
+                }
+            }
+        })
+        .unwrap()
+        .response
+        .rect;
+    // Keep the window while fast-forwarding
+    wos.fast_forward = open || state.running.is_some();
+    occupied_screen_space.push_egui_window_rect(rect);
+}
+
+fn form_ui(ui: &mut egui::Ui, form: &mut FastForwardForm, params: &Params) {
+    egui::Grid::new("fast_forward_form").show(ui, |ui| {
+        ui.label(t!("fast-forward-max-cycles"));
+        ui.add(egui::DragValue::new(&mut form.cycles).range(1..=1_000_000));
+        ui.end_row();
+
+        ui.checkbox(&mut form.use_requirement, t!("fast-forward-requirement"));
+        ui.add_enabled_ui(form.use_requirement, |ui| {
+            egui::ComboBox::from_id_salt("fast_forward_requirement")
+                .selected_text(t!("fast_forward_requirement", form.requirement))
+                .show_ui(ui, |ui| {
+                    for kind in RequirementKind::iter() {
+                        ui.selectable_value(
+                            &mut form.requirement,
+                            kind,
+                            t!("fast_forward_requirement", kind),
+                        );
+                    }
+                });
+        });
+        ui.end_row();
+
+        ui.label("");
+        ui.add_enabled_ui(form.use_requirement, |ui| {
+            ui.horizontal(|ui| requirement_ui(ui, form, params));
+        });
+        ui.end_row();
+    });
+
+    ui.label(t!("fast-forward-reports"));
+    for kind in ReportKind::iter() {
+        let mut checked = form.reports.contains(&kind);
+        if ui
+            .checkbox(&mut checked, t!("fast_forward_report", kind.as_ref()))
+            .changed()
+        {
+            if checked {
+                form.reports.push(kind);
+            } else {
+                form.reports.retain(|k| *k != kind);
+            }
+        }
+    }
+}
+
+fn requirement_ui(ui: &mut egui::Ui, form: &mut FastForwardForm, params: &Params) {
+    match form.requirement {
+        RequirementKind::PartialPressure => {
+            egui::ComboBox::from_id_salt("fast_forward_gas")
+                .selected_text(t!(form.gas))
+                .show_ui(ui, |ui| {
+                    for gas in GasKind::iter() {
+                        ui.selectable_value(&mut form.gas, gas, t!(gas));
+                    }
+                });
+            ui.add(
+                egui::DragValue::new(&mut form.partial_pressure)
+                    .speed(0.01)
+                    .range(0.0..=100.0)
+                    .suffix(" atm"),
+            );
+            return;
+        }
+        RequirementKind::BiomeTiles => {
+            egui::ComboBox::from_id_salt("fast_forward_biome")
+                .selected_text(t!(form.biome))
+                .show_ui(ui, |ui| {
+                    for biome in params.biome_list() {
+                        ui.selectable_value(&mut form.biome, biome, t!(biome));
+                    }
+                });
+        }
+        RequirementKind::AnimalTiles => (),
+        RequirementKind::Settlements => {
+            let selected_text = if let Some(animal_id) = &form.animal_id {
+                t!("animal", animal_id)
+            } else {
+                t!("fast-forward-any-animal")
+            };
+            egui::ComboBox::from_id_salt("fast_forward_animal")
+                .selected_text(selected_text)
+                .show_ui(ui, |ui| {
+                    ui.selectable_value(&mut form.animal_id, None, t!("fast-forward-any-animal"));
+                    for animal_id in params.animals.keys() {
+                        ui.selectable_value(
+                            &mut form.animal_id,
+                            Some(*animal_id),
+                            t!("animal", animal_id),
+                        );
+                    }
+                });
+        }
+        RequirementKind::StructureBuilt => {
+            egui::ComboBox::from_id_salt("fast_forward_structure")
+                .selected_text(t!(form.structure))
+                .show_ui(ui, |ui| {
+                    for kind in params.structure_list() {
+                        ui.selectable_value(&mut form.structure, kind, t!(kind));
+                    }
+                });
+        }
+    }
+    ui.add(egui::DragValue::new(&mut form.n).range(1..=100_000));
+}
@@ -78,7 +78,9 @@ impl Default for HelpItem {
 
 impl From<ItemGroup> for HelpItem {
     fn from(group: ItemGroup) -> HelpItem {
-        ITEM_LIST[&group][0]
+        ITEM_LIST
+            .get(&group)
+            .map_or(HelpItem::Biomes(Biome::default()), |items| items[0])
     }
 }
 
@@ -89,32 +91,10 @@ static ITEM_LIST: LazyLock<BTreeMap<ItemGroup, Vec<HelpItem>>> = LazyLock::new(|
         ItemGroup::Basics,
         BASIC_ITEMS.iter().map(|item| HelpItem::Basics(item)).collect(),
     );
-    map.insert(
-        ItemGroup::Facilities,
-        StructureKind::iter()
-            .filter_map(|structure_kind| {
-                if matches!(structure_kind, StructureKind::Settlement) {
-                    None
-                } else {
-                    Some(HelpItem::Facilities(structure_kind))
-                }
-            })
-            .collect(),
-    );
-    map.insert(
-        ItemGroup::SpaceBuildings,
-        SpaceBuildingKind::iter()
-            .map(HelpItem::SpaceBuildings)
-            .collect(),
-    );
     map.insert(
         ItemGroup::TileEvents,
         TileEventKind::iter().map(HelpItem::TileEvents).collect(),
     );
-    map.insert(
-        ItemGroup::Biomes,
-        Biome::iter().map(HelpItem::Biomes).collect(),
-    );
     map.insert(
         ItemGroup::Atmosphere,
         GasKind::iter().map(HelpItem::Atmosphere).collect(),
@@ -140,6 +120,25 @@ static ITEM_LIST: LazyLock<BTreeMap<ItemGroup, Vec<HelpItem>>> = LazyLock::new(|
     map
 });
 
+/// Items in the group. Facilities, space buildings and biomes are listed from params
+fn group_items(item_group: ItemGroup, params: &Params) -> Vec<HelpItem> {
+    match item_group {
+        ItemGroup::Facilities => params
+            .structure_list()
+            .into_iter()
+            .filter(|kind| kind.buildable_by_player())
+            .map(HelpItem::Facilities)
+            .collect(),
+        ItemGroup::SpaceBuildings => params
+            .space_building_list()
+            .into_iter()
+            .map(HelpItem::SpaceBuildings)
+            .collect(),
+        ItemGroup::Biomes => params.biome_list().into_iter().map(HelpItem::Biomes).collect(),
+        _ => ITEM_LIST[&item_group].clone(),
+    }
+}
+
 pub fn help_window(
     mut egui_ctxs: EguiContexts,
     mut occupied_screen_space: ResMut<OccupiedScreenSpace>,
@@ -168,11 +167,11 @@ pub fn help_window(
                             for item_group in ItemGroup::iter() {
                                 if ui
                                     .collapsing(t!(item_group), |ui| {
-                                        for item in &ITEM_LIST[&item_group] {
+                                        for item in group_items(item_group, &params) {
                                             if ui
                                                 .selectable_value(
                                                     &mut *current_item,
-                                                    *item,
+                                                    item,
                                                     t!(item),
                                                 )
                                                 .changed()
@@ -209,7 +208,7 @@ impl HelpItem {
     pub fn ui(&self, ui: &mut egui::Ui, textures: &UiTextures, params: &Params) {
         if let Some(building_attrs) = match self {
             HelpItem::Facilities(kind) => Some(&params.structures[kind].building),
-            HelpItem::SpaceBuildings(kind) => Some(&params.space_buildings[kind]),
+            HelpItem::SpaceBuildings(kind) => Some(&params.space_buildings[kind].building),
             _ => None,
         } {
             ui_building_attr(ui, textures, building_attrs);
@@ -266,13 +265,27 @@ fn ui_building_attr(ui: &mut egui::Ui, textures: &UiTextures, attrs: &BuildingAt
             "ui/icon-power",
             WithUnitDisplay::Power(attrs.power).to_string(),
         );
-    } else if let Some(BuildingEffect::ProduceMaterial { mass }) = &attrs.effect {
-        ui.label(egui::RichText::new(t!("produce")).strong());
-        label_with_icon(
-            ui,
-            textures,
-            "ui/icon-material",
-            WithUnitDisplay::Material(*mass).to_string(),
-        );
+    } else {
+        let products: Vec<_> = attrs
+            .effects
+            .iter()
+            .filter_map(|effect| match effect {
+                BuildingEffect::ProduceMaterial { mass } => Some((
+                    "ui/icon-material",
+                    WithUnitDisplay::Material(*mass).to_string(),
+                )),
+                BuildingEffect::ProduceGenePoint { value } => Some((
+                    "ui/icon-gene",
+                    WithUnitDisplay::GenePoint(*value).to_string(),
+                )),
+                _ => None,
+            })
+            .collect();
+        if !products.is_empty() {
+            ui.label(egui::RichText::new(t!("produce")).strong());
+        }
+        for (icon, s) in products {
+            label_with_icon(ui, textures, icon, s);
+        }
     }
 }
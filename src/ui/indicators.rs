@@ -296,6 +296,18 @@ pub fn tile_info_indicators(
             buried_carbon,
             "buried-carbon",
         ),
+        (
+            OverlayLayerKind::SurfaceUv,
+            "ui/icon-surface-uv",
+            format!("{:.1}", planet.tile_uv(p)),
+            "surface-uv",
+        ),
+        (
+            OverlayLayerKind::CloudCover,
+            "ui/icon-cloud-cover",
+            format!("{:.0} %", tile.cloud),
+            "cloud-cover",
+        ),
     ];
 
     for (layer, icon, label, s) in items {
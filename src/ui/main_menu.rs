@@ -130,7 +130,7 @@ pub fn main_menu(
                 }
             } else {
                 let mut start_params =
-                    crate::planet::start_planet_to_start_params(TUTORIAL_PLANET, &params);
+                    crate::planet::start_planet_to_start_params(TUTORIAL_PLANET, &params, None);
                 start_params.basics.name = t!(TUTORIAL_PLANET);
                 ew_manage_planet.send(ManagePlanet::New(start_params));
             }
@@ -1,5 +1,4 @@
 use std::collections::BTreeMap;
-use std::sync::LazyLock;
 
 use bevy::prelude::*;
 use bevy_egui::egui::epaint;
@@ -26,9 +25,13 @@ pub enum MapLayer {
     Fertility,
     Biomass,
     BuriedCarbon,
+    SurfaceUv,
+    CloudCover,
     Cities,
     Civilizations,
     Structures,
+    Wind,
+    OceanCurrent,
 }
 
 impl MapLayer {
@@ -41,9 +44,13 @@ impl MapLayer {
             Self::Fertility => "ui/icon-fertility",
             Self::Biomass => "ui/icon-biomass",
             Self::BuriedCarbon => "ui/icon-carbon",
+            Self::SurfaceUv => "ui/icon-surface-uv",
+            Self::CloudCover => "ui/icon-cloud-cover",
             Self::Cities => "ui/icon-city",
             Self::Civilizations => "ui/icon-civilization",
             Self::Structures => "ui/icon-build",
+            Self::Wind => "ui/icon-wind",
+            Self::OceanCurrent => "ui/icon-ocean-current",
         }
     }
 }
@@ -255,8 +262,14 @@ fn map_img(
 
             let color = match map_layer {
                 MapLayer::Biome => {
-                    let biome = planet.map[(x, y)].biome;
-                    params.biomes[&biome].color
+                    if planet.is_river(p, params) {
+                        RIVER_COLOR
+                    } else if planet.map[p].lake_level.is_some() {
+                        LAKE_COLOR
+                    } else {
+                        let biome = planet.map[(x, y)].biome;
+                        params.biomes[&biome].color
+                    }
                 }
                 MapLayer::Height => {
                     color_materials.get_rgb(planet, p, OverlayLayerKind::Height, params)
@@ -276,22 +289,28 @@ fn map_img(
                 MapLayer::BuriedCarbon => {
                     color_materials.get_rgb(planet, p, OverlayLayerKind::BuriedCarbon, params)
                 }
+                MapLayer::SurfaceUv => {
+                    color_materials.get_rgb(planet, p, OverlayLayerKind::SurfaceUv, params)
+                }
+                MapLayer::CloudCover => {
+                    color_materials.get_rgb(planet, p, OverlayLayerKind::CloudCover, params)
+                }
                 MapLayer::Cities => {
                     if let Some(Structure::Settlement(settlement)) = &planet.map[(x, y)].structure {
                         CITY_COLORS[settlement.age as usize]
-                    } else if planet.map[(x, y)].biome.is_land() {
-                        params.biomes[&Biome::Rock].color
+                    } else if planet.map[(x, y)].biome.is_land(params) {
+                        params.biomes[&Biome::ROCK].color
                     } else {
-                        params.biomes[&Biome::Ocean].color
+                        params.biomes[&Biome::OCEAN].color
                     }
                 }
                 MapLayer::Civilizations => {
                     if let Some((id, _)) = &sim.domain[(x, y)] {
-                        params.animals[id].color
-                    } else if planet.map[(x, y)].biome.is_land() {
-                        params.biomes[&Biome::Rock].color
+                        planet.animal_attr(id, params).color
+                    } else if planet.map[(x, y)].biome.is_land(params) {
+                        params.biomes[&Biome::ROCK].color
                     } else {
-                        params.biomes[&Biome::Ocean].color
+                        params.biomes[&Biome::OCEAN].color
                     }
                 }
                 MapLayer::Structures => {
@@ -299,19 +318,21 @@ fn map_img(
                         .structure
                         .as_ref()
                         .map(|s| s.kind())
-                        .and_then(|kind| {
-                            if matches!(kind, StructureKind::Settlement) {
-                                None
-                            } else {
-                                Some(kind)
-                            }
-                        })
+                        .filter(|kind| kind.buildable_by_player())
                     {
-                        STRUCTURE_COLORS[&kind]
-                    } else if planet.map[(x, y)].biome.is_land() {
-                        params.biomes[&Biome::Rock].color
+                        params.structures[&kind].color
+                    } else if planet.map[(x, y)].biome.is_land(params) {
+                        params.biomes[&Biome::ROCK].color
+                    } else {
+                        params.biomes[&Biome::OCEAN].color
+                    }
+                }
+                MapLayer::Wind => flow_color(sim.wind[p]),
+                MapLayer::OceanCurrent => {
+                    if planet.is_ocean_tile(p) {
+                        flow_color(sim.ocean_current[p])
                     } else {
-                        params.biomes[&Biome::Ocean].color
+                        params.biomes[&Biome::ROCK].color
                     }
                 }
             };
@@ -372,22 +393,10 @@ impl Legend {
         )
         .with_main_wrap(true);
 
-        // Biome order in map legend
-        let biomes = [
-            Biome::Rock,
-            Biome::Ocean,
-            Biome::IceSheet,
-            Biome::SeaIce,
-            Biome::Desert,
-            Biome::Tundra,
-            Biome::Grassland,
-            Biome::BorealForest,
-            Biome::TemperateForest,
-            Biome::TropicalRainforest,
-        ];
-        let biome_colors: Vec<_> = biomes
-            .iter()
-            .map(|biome| (*biome, params.biomes[biome].color))
+        let biome_colors: Vec<_> = params
+            .biome_list()
+            .into_iter()
+            .map(|biome| (biome, params.biomes[&biome].color))
             .collect();
 
         Self {
@@ -404,7 +413,9 @@ impl Legend {
             | MapLayer::Rainfall
             | MapLayer::Fertility
             | MapLayer::Biomass
-            | MapLayer::BuriedCarbon => {
+            | MapLayer::BuriedCarbon
+            | MapLayer::SurfaceUv
+            | MapLayer::CloudCover => {
                 ui_high_low(ui, self.gradation_images[0].1);
             }
             MapLayer::Height => {
@@ -427,7 +438,17 @@ impl Legend {
                 self.ui_color_legend(ui, legend_items.into_iter());
             }
             MapLayer::Structures => {
-                let legend_items = STRUCTURE_COLORS.iter().map(|(kind, color)| (*color, t!(kind)));
+                let legend_items = params
+                    .structure_list()
+                    .into_iter()
+                    .filter(|kind| kind.buildable_by_player())
+                    .map(|kind| (params.structures[&kind].color, t!(kind)));
+                self.ui_color_legend(ui, legend_items);
+            }
+            MapLayer::Wind | MapLayer::OceanCurrent => {
+                let legend_items = WIND_COLORS
+                    .iter()
+                    .map(|(direction, color)| (*color, t!("wind", direction)));
                 self.ui_color_legend(ui, legend_items);
             }
         }
@@ -463,21 +484,46 @@ const CITY_COLORS: [[u8; 3]; CivilizationAge::LEN] = [
     [190, 0, 255],
 ];
 
-static STRUCTURE_COLORS: LazyLock<BTreeMap<StructureKind, [u8; 3]>> = LazyLock::new(|| {
-    let mut map = BTreeMap::new();
-    map.insert(StructureKind::OxygenGenerator, [0, 128, 255]);
-    map.insert(StructureKind::Rainmaker, [0, 255, 255]);
-    map.insert(StructureKind::FertilizationPlant, [255, 160, 0]);
-    map.insert(StructureKind::Heater, [255, 0, 0]);
-    map.insert(StructureKind::CarbonCapturer, [192, 192, 192]);
-    map.insert(StructureKind::GiftTower, [190, 0, 255]);
-    map
-});
+/// Color of river tiles on the biome map
+const RIVER_COLOR: [u8; 3] = [40, 110, 230];
+
+/// Color of lake tiles on the biome map
+const LAKE_COLOR: [u8; 3] = [60, 140, 200];
+
+/// Colors of wind or ocean currents flowing to each direction
+const WIND_COLORS: [(&str, [u8; 3]); 4] = [
+    ("east", [255, 128, 0]),
+    ("west", [0, 160, 255]),
+    ("north", [255, 64, 160]),
+    ("south", [64, 200, 64]),
+];
+
+/// Blend the colors of directions by the components, and darken weak flow
+fn flow_color((eastward, northward): (f32, f32)) -> [u8; 3] {
+    let weights = [
+        eastward.max(0.0),
+        (-eastward).max(0.0),
+        northward.max(0.0),
+        (-northward).max(0.0),
+    ];
+    let sum: f32 = weights.iter().sum();
+    if sum <= 0.0 {
+        return [0, 0, 0];
+    }
+    let speed = eastward.hypot(northward).min(1.0);
+    let mut color = [0.0; 3];
+    for ((_, c), w) in WIND_COLORS.iter().zip(weights) {
+        for (acc, c) in color.iter_mut().zip(c) {
+            *acc += *c as f32 * w / sum * speed;
+        }
+    }
+    color.map(|c| c as u8)
+}
 
 fn civilization_color_legends(planet: &Planet, params: &Params) -> Vec<([u8; 3], String)> {
     let mut civs = BTreeMap::new();
     for civ in &planet.civs {
-        let color = params.animals[civ.0].color;
+        let color = planet.animal_attr(civ.0, params).color;
         civs.insert(*civ.0, (color, planet.civ_name(*civ.0)));
     }
     civs.into_values().collect()
@@ -3,6 +3,7 @@ mod animals;
 mod control;
 mod debug_tools;
 mod dialogs;
+mod fast_forward;
 mod help;
 mod hover_tile_tooltip;
 mod indicators;
@@ -57,6 +58,7 @@ pub struct WindowsOpenState {
     pub achivements: bool,
     pub preferences: bool,
     pub debug_tools: bool,
+    pub fast_forward: bool,
     pub dialogs: Vec<Dialog>,
     pub error_popup: Option<ManagePlanetError>,
 }
@@ -76,6 +78,7 @@ impl Default for WindowsOpenState {
             achivements: false,
             preferences: false,
             debug_tools: false,
+            fast_forward: false,
             dialogs: Vec::new(),
             error_popup: None,
         }
@@ -148,6 +151,7 @@ impl Plugin for UiPlugin {
                     dialogs::dialogs,
                     preferences::preferences_window,
                     debug_tools::debug_tools_window,
+                    fast_forward::fast_forward_window,
                     reset_window_open_state,
                 )
                     .run_if(in_state(GameState::Running))
@@ -261,6 +265,16 @@ fn load_textures(
             None
         }
     });
+    let space_building_imgs = ui_assets
+        .space_building_imgs
+        .iter()
+        .filter_map(|(path, handle)| {
+            if let Ok(handle) = handle.clone().try_typed::<Image>() {
+                Some((path.clone(), handle))
+            } else {
+                None
+            }
+        });
     let animal_imgs = ui_assets.animal_imgs.iter().filter_map(|(path, handle)| {
         if let Ok(handle) = handle.clone().try_typed::<Image>() {
             Some((path.clone(), handle))
@@ -268,7 +282,10 @@ fn load_textures(
             None
         }
     });
-    let textures = ui.chain(start_planets).chain(animal_imgs);
+    let textures = ui
+        .chain(start_planets)
+        .chain(space_building_imgs)
+        .chain(animal_imgs);
 
     let mut egui_textures = HashMap::new();
     for (path, handle) in textures {
@@ -369,7 +386,7 @@ fn check_windows_open_state(
 }
 
 impl WindowsOpenState {
-    fn open_bools(&self) -> [bool; 12] {
+    fn open_bools(&self) -> [bool; 13] {
         [
             self.space_building,
             self.animals,
@@ -383,6 +400,7 @@ impl WindowsOpenState {
             self.achivements,
             self.preferences,
             self.debug_tools,
+            self.fast_forward,
         ]
     }
 }
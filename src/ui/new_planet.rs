@@ -18,6 +18,8 @@ pub struct NewPlanetState {
     planet: NewPlanetKind,
     pub(super) name: String,
     solar_constant: f32,
+    axial_tilt: f32,
+    eccentricity: f32,
     difference_in_elevation: f32,
     water: f32,
     nitrogen: f32,
@@ -36,6 +38,8 @@ impl NewPlanetState {
             planet: NewPlanetKind::Id(params.start_planets[0].id.clone()),
             name: t!("new-planet"),
             solar_constant: params.custom_planet.solar_constant.default,
+            axial_tilt: params.custom_planet.axial_tilt.default,
+            eccentricity: params.custom_planet.eccentricity.default,
             difference_in_elevation: params.custom_planet.difference_in_elevation.default,
             water: params.custom_planet.water_volume.default_percentage,
             nitrogen: params.custom_planet.nitrogen.default_percentage,
@@ -168,7 +172,7 @@ pub fn new_planet(
 
 fn start(ew_manage_planet: &mut EventWriter<ManagePlanet>, params: &Params, state: &MainMenuState) {
     let mut start_params = match &state.new_planet.planet {
-        NewPlanetKind::Id(id) => crate::planet::start_planet_to_start_params(id, params),
+        NewPlanetKind::Id(id) => crate::planet::start_planet_to_start_params(id, params, None),
         NewPlanetKind::Custom => {
             let mut atmo = params.default_start_params.atmo.clone();
             *atmo.get_mut(&GasKind::Nitrogen).unwrap() =
@@ -180,6 +184,8 @@ fn start(ew_manage_planet: &mut EventWriter<ManagePlanet>, params: &Params, stat
             StartParams {
                 basics: Basics {
                     solar_constant: state.new_planet.solar_constant,
+                    axial_tilt: state.new_planet.axial_tilt,
+                    eccentricity: state.new_planet.eccentricity,
                     origin: "custom".into(),
                     ..params.default_start_params.clone().basics
                 },
@@ -249,6 +255,32 @@ fn custom(
         se_player.play_if_stopped("slider");
     }
 
+    if ui
+        .add(
+            egui::Slider::new(
+                &mut state.new_planet.axial_tilt,
+                npp.axial_tilt.min..=npp.axial_tilt.max,
+            )
+            .text(format!("{} [°]", t!("axial-tilt"))),
+        )
+        .changed()
+    {
+        se_player.play_if_stopped("slider");
+    }
+
+    if ui
+        .add(
+            egui::Slider::new(
+                &mut state.new_planet.eccentricity,
+                npp.eccentricity.min..=npp.eccentricity.max,
+            )
+            .text(t!("eccentricity")),
+        )
+        .changed()
+    {
+        se_player.play_if_stopped("slider");
+    }
+
     if ui
         .add(
             egui::Slider::new(
@@ -4,12 +4,11 @@ use bevy_egui::{
     egui::{self, epaint, load::SizedTexture},
 };
 use compact_str::format_compact;
-use strum::IntoEnumIterator;
 
 use super::{
     HELP_TOOLTIP_WIDTH, OccupiedScreenSpace, UiTextures, WindowsOpenState, help::HelpItem,
 };
-use crate::{audio::SoundEffectPlayer, planet::*};
+use crate::{audio::SoundEffectPlayer, planet::*, sim_worker::SimWorker};
 
 const BUILDING_BACKGROUND_SIZE: (u32, u32) = (336, 48);
 
@@ -18,19 +17,21 @@ pub fn space_buildings_window(
     mut occupied_screen_space: ResMut<OccupiedScreenSpace>,
     mut wos: ResMut<WindowsOpenState>,
     mut planet: ResMut<Planet>,
-    mut sim: ResMut<Sim>,
+    mut worker: ResMut<SimWorker>,
     window: bevy::prelude::Query<
         &mut bevy::window::Window,
         bevy::prelude::With<bevy::window::PrimaryWindow>,
     >,
     textures: Res<UiTextures>,
     params: Res<Params>,
+    replay: Option<Res<Replay>>,
     se_player: SoundEffectPlayer,
 ) {
     if !wos.space_building {
         return;
     }
     let window_width = window.get_single().unwrap().width();
+    let control_values = planet.control_values();
 
     let rect = egui::Window::new("space-buildings-window")
         .anchor(
@@ -50,9 +51,12 @@ pub fn space_buildings_window(
                 ui.heading(t!("space-buildings"));
             });
             ui.separator();
+            if replay.is_some() {
+                ui.disable();
+            }
 
             egui::ScrollArea::vertical().show(ui, |ui| {
-                for (i, kind) in SpaceBuildingKind::iter().enumerate() {
+                for (i, kind) in params.space_building_list().into_iter().enumerate() {
                     if i != 0 {
                         ui.separator();
                     }
@@ -61,7 +65,7 @@ pub fn space_buildings_window(
                             ui,
                             kind,
                             &mut planet,
-                            &mut sim,
+                            &mut worker,
                             &textures,
                             &params,
                             params.building_attrs(kind),
@@ -76,13 +80,17 @@ pub fn space_buildings_window(
         .response
         .rect;
     occupied_screen_space.push_egui_window_rect(rect);
+
+    if planet.control_values() != control_values {
+        worker.send_control(&planet);
+    }
 }
 
 pub fn buildng_row(
     ui: &mut egui::Ui,
     kind: SpaceBuildingKind,
     planet: &mut Planet,
-    sim: &mut Sim,
+    worker: &mut SimWorker,
     textures: &UiTextures,
     params: &Params,
     attrs: &BuildingAttrs,
@@ -105,7 +113,8 @@ pub fn buildng_row(
         ui.add(egui::Label::new(building_text).extend());
     });
 
-    let response = ui.add(BuildingImage::new(kind, building.n, textures));
+    let background = params.space_buildings[&kind].background;
+    let response = ui.add(BuildingImage::new(kind, building.n, background, textures));
     if response.hovered() {
         let help_item = HelpItem::SpaceBuildings(kind);
         let right_top = response.rect.right_top();
@@ -132,7 +141,7 @@ pub fn buildng_row(
             ui.add_enabled(false, egui::Button::new("+1"))
                 .on_disabled_hover_ui(|ui| cannot_build_reason.ui(ui, textures));
         } else if ui.button("+1").clicked() {
-            planet.build_space_building(kind, sim, params);
+            worker.apply_action(PlayerAction::BuildSpaceBuilding { kind }, planet, params);
             se_player.play("build-space");
         }
 
@@ -141,13 +150,9 @@ pub fn buildng_row(
                 ui.add_enabled(false, egui::Button::new("+5"))
                     .on_disabled_hover_ui(|ui| cannot_build_reason.ui(ui, textures));
             } else if ui.button("+5").clicked() {
+                // The worker stops building when the limit or resources are reached
                 for _ in 0..5 {
-                    if planet.buildable(attrs).is_ok() && build_max > planet.space_building(kind).n
-                    {
-                        planet.build_space_building(kind, sim, params);
-                    } else {
-                        break;
-                    }
+                    worker.apply_action(PlayerAction::BuildSpaceBuilding { kind }, planet, params);
                 }
                 se_player.play("build-space");
             }
@@ -156,11 +161,13 @@ pub fn buildng_row(
         if attrs.power < 0.0 {
             if n > 0 {
                 if ui.button("-1").clicked() {
-                    planet.demolish_space_building(kind, 1, sim, params);
+                    let action = PlayerAction::DemolishSpaceBuilding { kind, n: 1 };
+                    worker.apply_action(action, planet, params);
                     se_player.play("demolish");
                 }
                 if build_max >= 5 && ui.button("-5").clicked() {
-                    planet.demolish_space_building(kind, 5, sim, params);
+                    let action = PlayerAction::DemolishSpaceBuilding { kind, n: 5 };
+                    worker.apply_action(action, planet, params);
                     se_player.play("demolish");
                 }
             } else {
@@ -178,7 +185,7 @@ pub fn buildng_row(
         BuildingControlValue::IncreaseRate(rate) => {
             ui.spacing_mut().slider_width = 220.0;
             if ui
-                .add(egui::Slider::new(rate, -100..=100).suffix("%"))
+                .add(egui::Slider::new(rate, attrs.control_rate_range()).suffix("%"))
                 .changed()
             {
                 se_player.play_if_stopped("slider");
@@ -196,25 +203,28 @@ struct BuildingImage {
 }
 
 impl BuildingImage {
-    fn new(kind: SpaceBuildingKind, n: u32, textures: &UiTextures) -> Self {
-        let (background_star, left_padding) = match kind {
-            SpaceBuildingKind::DysonSwarmUnit => {
-                let t = textures.get("ui/background-building-star");
+    fn new(
+        kind: SpaceBuildingKind,
+        n: u32,
+        background: SpaceBuildingBackground,
+        textures: &UiTextures,
+    ) -> Self {
+        let (background_star, left_padding) = match background {
+            SpaceBuildingBackground::Space => (None, 0.0),
+            SpaceBuildingBackground::Star | SpaceBuildingBackground::PlanetLeft => {
+                let t = textures.get(format_compact!(
+                    "ui/background-building-{}",
+                    background.as_ref()
+                ));
                 let left_padding = t.size.x;
                 (Some(t), left_padding)
             }
-            SpaceBuildingKind::OrbitalMirror => {
+            SpaceBuildingBackground::Planet => {
                 (Some(textures.get("ui/background-building-planet")), 0.0)
             }
-            SpaceBuildingKind::IonIrradiator => {
-                let t = textures.get("ui/background-building-planet-left");
-                let left_padding = t.size.x;
-                (Some(t), left_padding)
-            }
-            _ => (None, 0.0),
         };
         Self {
-            building: textures.get(format_compact!("ui/building-{}", kind.as_ref())),
+            building: textures.get(format_compact!("space_buildings/{}", kind.as_ref())),
             background: textures.get("ui/background-building-space"),
             background_star,
             left_padding,
@@ -192,6 +192,15 @@ fn atmo_stat(ui: &mut egui::Ui, textures: &UiTextures, planet: &Planet, params:
         t!("average-air-temperature"),
         planet.stat.average_air_temp - KELVIN_CELSIUS
     ));
+    if planet.has_seasons() {
+        let (min, max) = planet.stat.seasonal_air_temp_range();
+        ui.label(format!(
+            "{}: {:.1} - {:.1} °C",
+            t!("seasonal-air-temperature"),
+            min - KELVIN_CELSIUS,
+            max - KELVIN_CELSIUS
+        ));
+    }
     ui.label(format!(
         "{}: {:.0} mm",
         t!("average-rainfall"),
@@ -203,6 +212,21 @@ fn atmo_stat(ui: &mut egui::Ui, textures: &UiTextures, planet: &Planet, params:
         t!("atmosphere-pressure"),
         planet.atmo.atm()
     ));
+    let hover_text = t!("help", "atmospheric-escape");
+    ui.label(format!(
+        "{}: {:.2e} Mt/cycle",
+        t!("atmospheric-escape"),
+        planet.atmo.escape_rate
+    ))
+    .on_hover_text(&hover_text);
+    if planet.water.escape_rate > 0.0 {
+        ui.label(format!(
+            "{}: {:.2e} m³/cycle",
+            t!("water-escape"),
+            planet.water.escape_rate
+        ))
+        .on_hover_text(&hover_text);
+    }
     ui.separator();
 
     egui::Grid::new("grid_atmo")
@@ -225,6 +249,42 @@ fn atmo_stat(ui: &mut egui::Ui, textures: &UiTextures, planet: &Planet, params:
             }
         });
 
+    ui.separator();
+    let hover_text = t!("help", "ocean-carbon");
+    ui.horizontal(|ui| {
+        ui.image(textures.get("ui/icon-ocean-carbon"))
+            .on_hover_text(&hover_text);
+        ui.label(format!(
+            "{}: {:.1} Gt",
+            t!("ocean-carbon"),
+            planet.water.dissolved_carbon * 1e-3
+        ))
+        .on_hover_text(&hover_text);
+    });
+    let hover_text = t!("help", "weathering");
+    ui.label(format!(
+        "{}: {:.2e} Mt/cycle",
+        t!("weathering"),
+        planet.water.weathering_rate
+    ))
+    .on_hover_text(&hover_text);
+
+    ui.separator();
+    let hover_text = t!("help", "ozone-layer");
+    ui.label(format!(
+        "{}: {:.0} DU",
+        t!("ozone-layer"),
+        planet.atmo.ozone
+    ))
+    .on_hover_text(&hover_text);
+    let hover_text = t!("help", "surface-uv");
+    ui.horizontal(|ui| {
+        ui.image(textures.get("ui/icon-surface-uv"))
+            .on_hover_text(&hover_text);
+        ui.label(format!("{:.2}", planet.atmo.surface_uv))
+            .on_hover_text(&hover_text);
+    });
+
     ui.separator();
     let hover_text = t!("help", "cloud-albedo");
     ui.horizontal(|ui| {
@@ -370,8 +430,9 @@ fn history_stat(
     let min_bound_margin = match item {
         GraphItem::AverageAirTemperature | GraphItem::AverageSeaTemperature => 1.0e-1,
         GraphItem::AverageRainfall => 1.0e+1,
-        GraphItem::Biomass | GraphItem::BuriedCarbon => 1.0e+0,
+        GraphItem::Biomass | GraphItem::BuriedCarbon | GraphItem::OceanCarbon => 1.0e+0,
         GraphItem::Oxygen | GraphItem::Nitrogen | GraphItem::CarbonDioxide => 1.0e-5,
+        GraphItem::Methane => 1.0e-7,
         GraphItem::Population => 1.0e+1,
     };
     let bound_margin = (max - min) * 0.08 + min_bound_margin;
@@ -408,7 +469,9 @@ pub enum GraphItem {
     Oxygen,
     Nitrogen,
     CarbonDioxide,
+    Methane,
     BuriedCarbon,
+    OceanCarbon,
     Population,
 }
 
@@ -428,9 +491,13 @@ impl GraphItem {
             Self::Oxygen => record.map(|record| record.p_o2 as f64).unwrap_or(0.0),
             Self::Nitrogen => record.map(|record| record.p_n2 as f64).unwrap_or(0.0),
             Self::CarbonDioxide => record.map(|record| record.p_co2 as f64).unwrap_or(0.0),
+            Self::Methane => record.map(|record| record.p_ch4 as f64).unwrap_or(0.0),
             Self::BuriedCarbon => record
                 .map(|record| record.buried_carbon as f64 / 1000.0)
                 .unwrap_or(0.0),
+            Self::OceanCarbon => record
+                .map(|record| record.ocean_carbon as f64 / 1000.0)
+                .unwrap_or(0.0),
             Self::Population => record.map(|record| record.pop(None) as f64).unwrap_or(0.0),
         }
     }
@@ -444,7 +511,9 @@ impl GraphItem {
             Self::Oxygen => format!("{value:.2e} atm"),
             Self::Nitrogen => format!("{value:.2e} atm"),
             Self::CarbonDioxide => format!("{value:.2e} atm"),
+            Self::Methane => format!("{value:.2e} atm"),
             Self::BuriedCarbon => format!("{value:.1} Gt"),
+            Self::OceanCarbon => format!("{value:.1} Gt"),
             Self::Population => format!("{value:.0}"),
         }
     }
@@ -458,7 +527,9 @@ impl GraphItem {
             Self::Oxygen => "ui/icon-oxygen",
             Self::Nitrogen => "ui/icon-nitrogen",
             Self::CarbonDioxide => "ui/icon-carbon-dioxide",
+            Self::Methane => "ui/icon-methane",
             Self::BuriedCarbon => "ui/icon-carbon",
+            Self::OceanCarbon => "ui/icon-ocean-carbon",
             Self::Population => "ui/icon-population",
         }
     }
@@ -1,6 +1,5 @@
 use bevy::{app::AppExit, prelude::*};
 use bevy_egui::{EguiContexts, egui};
-use strum::IntoEnumIterator;
 
 use crate::{
     GameSpeed, GameState,
@@ -9,9 +8,10 @@ use crate::{
     conf::Conf,
     draw::{DisplayOpts, UpdateDraw},
     manage_planet::ManagePlanet,
-    planet::{Params, Planet, StructureKind},
+    planet::{Params, Planet},
     screen::{CursorMode, OccupiedScreenSpace},
     text::WithUnitDisplay,
+    undo::{UndoHistory, UndoRedo},
 };
 
 use super::{UiTextures, WindowsOpenState, help::HelpItem, misc::label_with_icon};
@@ -30,6 +30,7 @@ pub fn toolbar(
     mut achivement_notification: ResMut<AchivementNotification>,
     (mut display_opts, mut update_draw): (ResMut<DisplayOpts>, ResMut<UpdateDraw>),
     (textures, planet, params, conf): (Res<UiTextures>, Res<Planet>, Res<Params>, Res<Conf>),
+    (history, mut ew_undo_redo): (Res<UndoHistory>, EventWriter<UndoRedo>),
     se_player: SoundEffectPlayer,
     mut right_ui_width: Local<f32>,
 ) {
@@ -54,6 +55,7 @@ pub fn toolbar(
                     &params,
                     &mut achivement_notification,
                     (&mut display_opts, &mut update_draw),
+                    (&history, &mut ew_undo_redo),
                     &se_player,
                     &mut right_ui_width,
                 );
@@ -83,6 +85,7 @@ fn toolbar_ui(
     params: &Params,
     achivement_notification: &mut AchivementNotification,
     (display_opts, update_draw): (&mut DisplayOpts, &mut UpdateDraw),
+    (history, ew_undo_redo): (&UndoHistory, &mut EventWriter<UndoRedo>),
     se_player: &SoundEffectPlayer,
     right_ui_width: &mut f32,
 ) {
@@ -104,6 +107,7 @@ fn toolbar_ui(
                 game_menu(
                     ui,
                     wos,
+                    planet,
                     app_exit_events,
                     ew_manage_planet,
                     next_game_state,
@@ -116,7 +120,7 @@ fn toolbar_ui(
         ui.add(egui::Separator::default().spacing(2.0).vertical());
 
         let menu_clicked = egui::menu::menu_custom_button(ui, menu_button("ui/icon-build"), |ui| {
-            build_menu(ui, cursor_mode, textures, params, se_player);
+            build_menu(ui, cursor_mode, textures, planet, params, se_player);
         })
         .response
         .clicked()
@@ -173,6 +177,30 @@ fn toolbar_ui(
 
         ui.add(egui::Separator::default().spacing(2.0).vertical());
 
+        // Undo and redo
+        if ui
+            .add_enabled(
+                history.can_undo(),
+                egui::ImageButton::new(textures.get("ui/icon-undo")),
+            )
+            .on_hover_text(t!("undo"))
+            .clicked()
+        {
+            ew_undo_redo.send(UndoRedo::Undo);
+        }
+        if ui
+            .add_enabled(
+                history.can_redo(),
+                egui::ImageButton::new(textures.get("ui/icon-redo")),
+            )
+            .on_hover_text(t!("redo"))
+            .clicked()
+        {
+            ew_undo_redo.send(UndoRedo::Redo);
+        }
+
+        ui.add(egui::Separator::default().spacing(2.0).vertical());
+
         // Game speed selector
         let texture = if *speed == GameSpeed::Paused {
             "ui/icon-speed-paused-selected"
@@ -214,6 +242,10 @@ fn toolbar_ui(
             se_player.play("select-item");
         }
 
+        if button(ui, "ui/icon-fast-forward", "fast-forward") {
+            wos.fast_forward = !wos.fast_forward;
+        }
+
         let hover_text = t!("stat_item", "cycles");
         ui.image(textures.get("ui/icon-cycles"))
             .on_hover_text(&hover_text);
@@ -256,6 +288,7 @@ fn build_menu(
     ui: &mut egui::Ui,
     cursor_mode: &mut CursorMode,
     textures: &UiTextures,
+    planet: &Planet,
     params: &Params,
     se_player: &SoundEffectPlayer,
 ) {
@@ -266,7 +299,11 @@ fn build_menu(
     }
     ui.separator();
     let pos_tooltip = ui.response().rect.right_top() + egui::Vec2::new(16.0, 0.0);
-    for kind in StructureKind::iter().filter(|kind| kind.buildable_by_player()) {
+    let structures = params
+        .structure_list()
+        .into_iter()
+        .filter(|kind| kind.buildable_by_player() && planet.structure_unlocked(*kind, params));
+    for kind in structures {
         let response = ui.button(t!(kind));
         if response.clicked() {
             *cursor_mode = CursorMode::Build(kind);
@@ -353,6 +390,7 @@ fn action_menu(
 fn game_menu(
     ui: &mut egui::Ui,
     wos: &mut WindowsOpenState,
+    planet: &Planet,
     app_exit_events: &mut EventWriter<AppExit>,
     ew_manage_planet: &mut EventWriter<ManagePlanet>,
     next_game_state: &mut NextState<GameState>,
@@ -374,6 +412,15 @@ fn game_menu(
         wos.load = true;
         ui.close_menu();
     }
+    if ui
+        .add_enabled(planet.journal.replayable(), egui::Button::new(t!("replay")))
+        .on_disabled_hover_text(t!("replay-disabled"))
+        .clicked()
+    {
+        ew_manage_planet.send(ManagePlanet::Replay);
+        ui.close_menu();
+        se_player.play("select-item");
+    }
     ui.separator();
     if ui.button(format!("{}...", t!("preferences"))).clicked() {
         wos.preferences = true;
@@ -0,0 +1,122 @@
+use std::collections::VecDeque;
+
+use bevy::prelude::*;
+
+use crate::audio::SoundEffectPlayer;
+use crate::conf::Conf;
+use crate::draw::UpdateDraw;
+use crate::manage_planet::SwitchPlanet;
+use crate::planet::*;
+use crate::sim_worker::SimWorker;
+use crate::{GameState, GameSystemSet};
+
+/// Maximum number of undo steps
+const UNDO_HISTORY_LIMIT: usize = 20;
+
+#[derive(Clone, Copy, Debug)]
+pub struct UndoPlugin;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Event)]
+pub enum UndoRedo {
+    Undo,
+    Redo,
+}
+
+/// Planet snapshots taken before player actions
+#[derive(Default, Debug, Resource)]
+pub struct UndoHistory {
+    undo: VecDeque<Planet>,
+    redo: Vec<Planet>,
+    /// Planet cycles at the last check
+    cycles: u64,
+}
+
+impl Plugin for UndoPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<UndoRedo>()
+            .init_resource::<UndoHistory>()
+            .add_systems(
+                Update,
+                update_history
+                    .run_if(in_state(GameState::Running))
+                    .before(GameSystemSet::Draw),
+            );
+    }
+}
+
+impl UndoHistory {
+    /// Record the planet state before a player action
+    pub fn record(&mut self, planet: &Planet) {
+        self.redo.clear();
+        self.undo.push_back(planet.clone());
+        if self.undo.len() > UNDO_HISTORY_LIMIT {
+            self.undo.pop_front();
+        }
+        self.cycles = planet.cycles;
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.undo.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+
+    fn clear(&mut self) {
+        self.undo.clear();
+        self.redo.clear();
+    }
+
+    /// Remove snapshots that are no longer valid after cycles advanced
+    fn on_cycles_changed(&mut self, cycles: u64, undo_cycles: u64) {
+        self.redo.clear();
+        self.undo.retain(|planet| planet.cycles + undo_cycles >= cycles);
+        self.cycles = cycles;
+    }
+}
+
+fn update_history(
+    mut er_undo_redo: EventReader<UndoRedo>,
+    mut er_switch_planet: EventReader<SwitchPlanet>,
+    mut history: ResMut<UndoHistory>,
+    mut planet: ResMut<Planet>,
+    mut worker: ResMut<SimWorker>,
+    mut update_draw: ResMut<UpdateDraw>,
+    conf: Res<Conf>,
+    se_player: SoundEffectPlayer,
+) {
+    if er_switch_planet.read().last().is_some() {
+        history.clear();
+        history.cycles = planet.cycles;
+    }
+    if planet.cycles != history.cycles {
+        history.on_cycles_changed(planet.cycles, conf.undo_cycles);
+    }
+
+    for e in er_undo_redo.read() {
+        let restored = match e {
+            UndoRedo::Undo => {
+                let Some(restored) = history.undo.pop_back() else {
+                    continue;
+                };
+                history.redo.push(planet.clone());
+                restored
+            }
+            UndoRedo::Redo => {
+                let Some(restored) = history.redo.pop() else {
+                    continue;
+                };
+                history.undo.push_back(planet.clone());
+                restored
+            }
+        };
+
+        log::info!("{:?} to cycles {}", e, restored.cycles);
+        worker.restore_planet(&restored);
+        *planet = restored;
+        history.cycles = planet.cycles;
+        update_draw.update();
+        se_player.play("select-item");
+    }
+}